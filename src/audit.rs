@@ -0,0 +1,100 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: audit.rs
+* Description: Data-quality checks for stored usernames. A broken scrape can
+* capture text with the wrong encoding or with stray invisible characters;
+* such entries never match the real handle, so they show up as phantom
+* unfollowers and new followers. The audit finds them so the user knows to
+* re-scrape.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+
+use crate::format::decode_any_followers;
+use crate::to_js;
+
+/// A username flagged by `audit_encoding`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EncodingAnomaly {
+    pub username: String,
+    /// `"replacement_character"`, `"control_character"`, `"invisible_character"`
+    /// or `"mojibake"`.
+    pub issue: &'static str,
+}
+
+/// Finds usernames that look damaged by a bad scrape.
+///
+/// Each username is checked for, in this order:
+/// - `"replacement_character"`: U+FFFD, left behind where bytes could not be decoded.
+/// - `"control_character"`: C0/C1 control characters such as NUL or a newline.
+/// - `"invisible_character"`: zero-width spaces and joiners or a byte order mark.
+/// - `"mojibake"`: UTF-8 that was decoded as Latin-1, like `Ã©` for `é`.
+///
+/// Only the first issue found is reported for each username. Blobs that are not
+/// valid UTF-8 at all cannot be decoded and are reported as an error instead.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing the follower blob, serialized in
+///   MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of `{ username, issue }` objects in blob order.
+///   Empty when every username looks clean.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn audit_encoding(mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let (_, usernames) = decode_any_followers(mpack, "followers")?;
+
+    let anomalies: Vec<EncodingAnomaly> = usernames
+        .into_iter()
+        .filter_map(|username| encoding_issue(&username).map(|issue| EncodingAnomaly { username, issue }))
+        .collect();
+
+    to_js(&anomalies)
+}
+
+/// Returns the first encoding issue found in `username`, if any.
+fn encoding_issue(username: &str) -> Option<&'static str> {
+    if username.contains('\u{FFFD}') {
+        return Some("replacement_character");
+    }
+    if username.chars().any(char::is_control) {
+        return Some("control_character");
+    }
+    if username.chars().any(|c| matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}')) {
+        return Some("invisible_character");
+    }
+    // A UTF-8 lead byte for U+0080..U+07FF read as Latin-1 is 'Â' or 'Ã'
+    // (0xC2, 0xC3), followed by a continuation byte in U+0080..U+00BF.
+    let chars: Vec<char> = username.chars().collect();
+    if chars.windows(2).any(|pair| matches!(pair[0], 'Â' | 'Ã') && matches!(pair[1], '\u{80}'..='\u{BF}')) {
+        return Some("mojibake");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_the_first_issue_of_each_kind() {
+        assert_eq!(encoding_issue("jo\u{FFFD}o"), Some("replacement_character"));
+        assert_eq!(encoding_issue("alice\n"), Some("control_character"));
+        assert_eq!(encoding_issue("al\u{200B}ice"), Some("invisible_character"));
+        assert_eq!(encoding_issue("\u{FEFF}alice"), Some("invisible_character"));
+        assert_eq!(encoding_issue("josÃ©"), Some("mojibake"));
+        // A replacement character wins over the control character that follows.
+        assert_eq!(encoding_issue("\u{FFFD}\u{0}"), Some("replacement_character"));
+    }
+
+    #[test]
+    fn clean_usernames_including_accents_pass() {
+        for username in ["alice", "josé", "björk_99", "日本語", "Ãlvaro"] {
+            assert_eq!(encoding_issue(username), None, "{}", username);
+        }
+    }
+}
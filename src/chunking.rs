@@ -0,0 +1,135 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: chunking.rs
+* Description: Sharding of large follower lists for chunked storage.
+* `chrome.storage` enforces per-item size limits, so a big follower blob may
+* have to be stored as several items. Chunks are split on username
+* boundaries, never inside one, and each chunk is itself a complete
+* MessagePack username list, so every chunk can be decoded on its own.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Array, Uint8Array};
+
+use crate::{decode_followers, encode_mpack, followers_from_js, to_js};
+
+/// Serializes a follower list into MessagePack chunks of bounded size.
+///
+/// Usernames are packed greedily in input order, so concatenating the chunks'
+/// contents with `dechunk_mpack` restores the original list.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+/// * `max_bytes`: The maximum size of each chunk, in bytes. Must be at least 1.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of `Uint8Array` chunks, each at most
+///   `max_bytes` long. An empty list produces a single empty-list chunk.
+/// - `Err(JsValue)`: A JavaScript error object if `max_bytes` is 0, if the input is
+///   invalid, or if a single username is too large to fit in a chunk on its own.
+#[wasm_bindgen]
+pub fn chunk_mpack(followers_js: JsValue, max_bytes: usize) -> Result<JsValue, JsValue> {
+    if max_bytes == 0 {
+        return Err(JsValue::from_str("Chunk size must be at least 1 byte"));
+    }
+    let followers = followers_from_js(followers_js, "followers for chunking")?;
+
+    let mut chunks: Vec<Vec<&str>> = vec![Vec::new()];
+    let mut payload_len = 0;
+    for username in &followers {
+        let item_len = encoded_str_len(username);
+        if array_header_len(1) + item_len > max_bytes {
+            return Err(JsValue::from_str(&format!(
+                "Username '{}' needs {} bytes and cannot fit in a {}-byte chunk",
+                username,
+                array_header_len(1) + item_len,
+                max_bytes
+            )));
+        }
+
+        let current = chunks.last_mut().expect("chunks always holds at least one chunk");
+        if array_header_len(current.len() + 1) + payload_len + item_len > max_bytes {
+            chunks.push(vec![username.as_str()]);
+            payload_len = item_len;
+        } else {
+            current.push(username);
+            payload_len += item_len;
+        }
+    }
+
+    let result = Array::new();
+    for chunk in &chunks {
+        result.push(&Uint8Array::from(encode_mpack(chunk)?.as_slice()));
+    }
+    Ok(result.into())
+}
+
+/// Reassembles chunks produced by `chunk_mpack` into the full username list.
+///
+/// # Arguments
+///
+/// * `chunks_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `Uint8Array` chunks, in the order `chunk_mpack` returned them.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of strings with every username, in order.
+/// - `Err(JsValue)`: A JavaScript error object if the input is not an array of
+///   `Uint8Array`s or a chunk cannot be deserialized.
+#[wasm_bindgen]
+pub fn dechunk_mpack(chunks_js: JsValue) -> Result<JsValue, JsValue> {
+    let chunks = chunks_js
+        .dyn_into::<Array>()
+        .map_err(|_| JsValue::from_str("Chunks must be an array of Uint8Array"))?;
+
+    let mut followers: Vec<String> = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let chunk = chunk
+            .dyn_into::<Uint8Array>()
+            .map_err(|_| JsValue::from_str(&format!("Chunk {} is not a Uint8Array", index)))?;
+        followers.extend(decode_followers(&chunk.to_vec(), &format!("chunk {}", index))?);
+    }
+
+    to_js(&followers)
+}
+
+/// The MessagePack-encoded size of a string: its marker plus length prefix,
+/// followed by the UTF-8 bytes.
+fn encoded_str_len(s: &str) -> usize {
+    let header = match s.len() {
+        0..=31 => 1,
+        32..=0xff => 2,
+        0x100..=0xffff => 3,
+        _ => 5,
+    };
+    header + s.len()
+}
+
+/// The MessagePack-encoded size of an array header for `count` elements.
+fn array_header_len(count: usize) -> usize {
+    match count {
+        0..=15 => 1,
+        16..=0xffff => 3,
+        _ => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_estimates_match_the_encoder() {
+        for len in [0, 31, 32, 255, 256, 70_000] {
+            let s = "é".repeat(len / 2) + &"a".repeat(len % 2);
+            assert_eq!(encoded_str_len(&s), rmp_serde::to_vec(&s).unwrap().len(), "string of {} bytes", s.len());
+        }
+        for count in [0, 15, 16, 70_000] {
+            let list = vec![0u8; count];
+            assert_eq!(array_header_len(count), rmp_serde::to_vec(&list).unwrap().len() - count);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: classify.rs
+* Description: Username heuristics for spotting bot churn. Bot and spam
+* accounts are usually created in bulk with generated handles such as
+* `user12839402`, so a high share of digits or a long run of digits is a good
+* hint that an unfollower was never a real person. The results are only a
+* hint for the UI; nothing here is used to alter the diffs themselves.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use crate::{followers_from_js, to_js};
+
+/// A run of at least this many consecutive digits marks a generated handle,
+/// whatever the overall digit ratio.
+const BOT_DIGIT_RUN: usize = 6;
+
+/// The `classify_unfollowers` digit ratio used when none is given.
+const DEFAULT_DIGIT_RATIO_THRESHOLD: f64 = 0.5;
+
+/// Unfollowers split by whether their username looks generated.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnfollowerClassification {
+    pub likely_bots: Vec<String>,
+    pub likely_real: Vec<String>,
+}
+
+/// Splits a list of unfollowers into likely bots and likely real accounts
+/// based on their usernames.
+///
+/// A username is classed as a likely bot when the share of its characters that
+/// are ASCII digits is at least `digit_ratio_threshold`, or when it contains a
+/// run of six or more consecutive digits, as in `user12839402`. Everything
+/// else, such as `jane.doe` or `mike_1990`, is classed as likely real.
+///
+/// # Arguments
+///
+/// * `unfollowers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   with the unfollowers, e.g. as returned by `find_unfollowers`.
+/// * `digit_ratio_threshold`: An optional share of digits, from `0.0` to `1.0`, at or
+///   above which a username is classed as a bot. Defaults to `0.5`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ likely_bots, likely_real }` of sorted
+///   string arrays.
+/// - `Err(JsValue)`: A JavaScript error object if the threshold is outside `0.0..=1.0`,
+///   or if deserialization fails.
+#[wasm_bindgen]
+pub fn classify_unfollowers(unfollowers_js: JsValue, digit_ratio_threshold: Option<f64>) -> Result<JsValue, JsValue> {
+    let digit_ratio_threshold = digit_ratio_threshold.unwrap_or(DEFAULT_DIGIT_RATIO_THRESHOLD);
+    if !(0.0..=1.0).contains(&digit_ratio_threshold) {
+        return Err(JsValue::from_str(&format!(
+            "Invalid digit ratio threshold {}: expected a number from 0 to 1",
+            digit_ratio_threshold
+        )));
+    }
+    let unfollowers: BTreeSet<String> = followers_from_js(unfollowers_js, "unfollowers")?.into_iter().collect();
+
+    let (likely_bots, likely_real) = unfollowers
+        .into_iter()
+        .partition(|username| looks_generated(username, digit_ratio_threshold));
+
+    to_js(&UnfollowerClassification { likely_bots, likely_real })
+}
+
+/// Applies the digit heuristics described on `classify_unfollowers`.
+fn looks_generated(username: &str, digit_ratio_threshold: f64) -> bool {
+    let total = username.chars().count();
+    if total == 0 {
+        return false;
+    }
+
+    let mut digits = 0;
+    let mut run = 0;
+    let mut longest_run = 0;
+    for c in username.chars() {
+        if c.is_ascii_digit() {
+            digits += 1;
+            run += 1;
+            longest_run = longest_run.max(run);
+        } else {
+            run = 0;
+        }
+    }
+
+    longest_run >= BOT_DIGIT_RUN || digits as f64 / total as f64 >= digit_ratio_threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_a_bot_style_username_but_not_a_normal_one() {
+        assert!(looks_generated("user12839402", DEFAULT_DIGIT_RATIO_THRESHOLD));
+        assert!(!looks_generated("jane.doe", DEFAULT_DIGIT_RATIO_THRESHOLD));
+        assert!(!looks_generated("mike_1990", DEFAULT_DIGIT_RATIO_THRESHOLD));
+    }
+
+    #[test]
+    fn digit_ratio_and_digit_run_are_independent_signals() {
+        // Half digits, but no run of six: only the ratio flags it.
+        assert!(looks_generated("ab12", 0.5));
+        assert!(!looks_generated("ab12", 0.6));
+        // A run of six digits is flagged whatever the threshold.
+        assert!(looks_generated("someone_123456", 1.0));
+        assert!(!looks_generated("", 0.0));
+    }
+}
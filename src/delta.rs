@@ -0,0 +1,131 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: delta.rs
+* Description: Delta encoding for follower snapshots. Rewriting the whole
+* follower list to storage after every check is wasteful when only a handful
+* of accounts changed. These functions express a check as two small blobs,
+* the usernames added and the usernames removed, and rebuild the new
+* snapshot from the old one plus those deltas.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Object, Reflect, Uint8Array};
+use std::collections::{BTreeSet, HashSet};
+
+use crate::error::validate_usernames;
+use crate::{decode_followers, diff_sets, encode_mpack, records_from_js, to_js, FollowerDiff};
+
+/// Computes the follower diff as two MessagePack blobs.
+///
+/// Feeding the old snapshot and both blobs to `apply_snapshot_delta`
+/// reconstructs the new snapshot's follower set.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ added_mpack, removed_mpack }` whose values
+///   are `Uint8Array`s holding sorted username lists.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn diff_as_delta_blobs(new_followers_js: JsValue, old_followers_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let (old_set, new_set) = diff_sets(new_followers_js, old_followers_mpack)?;
+    let diff = FollowerDiff::between(&old_set, &new_set, false);
+
+    let added = Uint8Array::from(encode_mpack(&diff.new_followers)?.as_slice());
+    let removed = Uint8Array::from(encode_mpack(&diff.unfollowers)?.as_slice());
+
+    let result = Object::new();
+    Reflect::set(&result, &JsValue::from_str("added_mpack"), &added)?;
+    Reflect::set(&result, &JsValue::from_str("removed_mpack"), &removed)?;
+    Ok(result.into())
+}
+
+/// Applies a delta produced by `diff_as_delta_blobs` to a stored snapshot.
+///
+/// Removed usernames are dropped from the old list, keeping its order, and
+/// added usernames are appended. Duplicates are collapsed.
+///
+/// # Arguments
+///
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `added_mpack`: The `added_mpack` blob of the delta.
+/// * `removed_mpack`: The `removed_mpack` blob of the delta.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The reconstructed follower list, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if any blob cannot be deserialized.
+#[wasm_bindgen]
+pub fn apply_snapshot_delta(
+    old_followers_mpack: &[u8],
+    added_mpack: &[u8],
+    removed_mpack: &[u8],
+) -> Result<Vec<u8>, JsValue> {
+    let old_followers = decode_followers(old_followers_mpack, "old followers")?;
+    let added = decode_followers(added_mpack, "added followers")?;
+    let removed: HashSet<String> = decode_followers(removed_mpack, "removed followers")?.into_iter().collect();
+
+    let mut seen: HashSet<String> = HashSet::with_capacity(old_followers.len() + added.len());
+    let followers: Vec<String> = old_followers
+        .into_iter()
+        .filter(|username| !removed.contains(username))
+        .chain(added)
+        .filter(|username| seen.insert(username.clone()))
+        .collect();
+
+    encode_mpack(&followers)
+}
+
+/// Applies a chronological series of raw scrapes to a stored snapshot in one call.
+///
+/// Each scrape is diffed against the one before it, starting from the stored
+/// snapshot, and the gains and losses of every step are accumulated. This is
+/// meant for backfilling several scrapes captured over a day without crossing
+/// the JS/WASM boundary once per scrape. An account that arrives and leaves
+/// again within the series is listed in both totals.
+///
+/// # Arguments
+///
+/// * `scrapes_js`: A `JsValue` from JavaScript, expected to be an array of scrapes in
+///   chronological order, each an array of strings.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ final_snapshot_mpack, total_gained, total_lost }`.
+///   `final_snapshot_mpack` is a `Uint8Array` holding the last scrape (or the stored
+///   snapshot if the series is empty); the totals are sorted arrays of strings.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn process_scrape_series(scrapes_js: JsValue, old_followers_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let mut current = decode_followers(old_followers_mpack, "old followers")?;
+    let scrapes: Vec<Vec<String>> = records_from_js(scrapes_js, "scrapes")?;
+
+    let mut gained: BTreeSet<String> = BTreeSet::new();
+    let mut lost: BTreeSet<String> = BTreeSet::new();
+    for scrape in scrapes {
+        validate_usernames(&scrape)?;
+        let previous: HashSet<&String> = current.iter().collect();
+        let next: HashSet<&String> = scrape.iter().collect();
+        gained.extend(next.difference(&previous).map(|username| (*username).clone()));
+        lost.extend(previous.difference(&next).map(|username| (*username).clone()));
+        current = scrape;
+    }
+
+    let final_snapshot = Uint8Array::from(encode_mpack(&current)?.as_slice());
+    let result = Object::new();
+    Reflect::set(&result, &JsValue::from_str("final_snapshot_mpack"), &final_snapshot)?;
+    Reflect::set(&result, &JsValue::from_str("total_gained"), &to_js(&gained)?)?;
+    Reflect::set(&result, &JsValue::from_str("total_lost"), &to_js(&lost)?)?;
+    Ok(result.into())
+}
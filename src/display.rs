@@ -0,0 +1,47 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: display.rs
+* Description: Helpers for presenting usernames in the UI. Instagram handles
+* are ASCII, but display names captured alongside them and handles from other
+* platforms may contain emoji and combining characters, which must never be
+* cut in the middle when shortened for a compact list.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{followers_from_js, to_js};
+
+/// Shortens usernames for display without splitting a grapheme cluster.
+///
+/// Length is measured in grapheme clusters, i.e. user-perceived characters, so
+/// an emoji built from several code points, such as a flag or a family, counts
+/// as one and is either kept whole or dropped whole. A username longer than
+/// `max_graphemes` is cut to that many clusters and `ellipsis` is appended;
+/// shorter ones are returned unchanged.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+/// * `max_graphemes`: The number of grapheme clusters to keep.
+/// * `ellipsis`: The text appended to a truncated username, e.g. `"…"`. It is not
+///   counted towards `max_graphemes`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of strings, in input order.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid.
+#[wasm_bindgen]
+pub fn truncate_usernames(followers_js: JsValue, max_graphemes: usize, ellipsis: &str) -> Result<JsValue, JsValue> {
+    let followers = followers_from_js(followers_js, "followers")?;
+
+    let truncated: Vec<String> = followers
+        .into_iter()
+        .map(|username| match username.grapheme_indices(true).nth(max_graphemes) {
+            Some((cut, _)) => format!("{}{}", &username[..cut], ellipsis),
+            None => username,
+        })
+        .collect();
+
+    to_js(&truncated)
+}
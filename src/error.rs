@@ -0,0 +1,191 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: error.rs
+* Description: The crate's structured error type. Most failures are reported
+* to JavaScript as plain error strings; `TrackerError` gives the ones that
+* callers may want to recognise a dedicated variant with a consistent message.
+* Every variant converts into a `JsValue` string so it can be returned
+* directly from exported functions.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use std::fmt;
+
+/// The longest username, in characters, accepted from JavaScript input.
+///
+/// Real Instagram handles are limited to 30 characters; the higher cap leaves
+/// room for other platforms while still rejecting garbage such as a whole DOM
+/// subtree captured by a broken selector.
+pub const MAX_USERNAME_CHARS: usize = 100;
+
+/// Errors reported by the tracker.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackerError {
+    /// A username exceeded `MAX_USERNAME_CHARS`. `preview` holds its first few
+    /// characters to help locate the bad entry.
+    UsernameTooLong { length: usize, max: usize, preview: String },
+    /// A timestamp argument was `NaN`, infinite or negative. `name` is the
+    /// argument that was rejected.
+    InvalidTimestamp { name: &'static str, value: f64 },
+    /// A duration argument, such as a window or grace period, was `NaN`,
+    /// infinite or negative. `name` is the argument that was rejected.
+    InvalidDuration { name: &'static str, value: f64 },
+    /// A MessagePack blob could not be decoded. `bytes` is the blob's length,
+    /// which makes bug reports about truncated or foreign blobs actionable.
+    BlobDecode { what: String, bytes: usize, detail: String },
+    /// A value passed from JavaScript could not be decoded. `elements` is the
+    /// array length when the value was an array.
+    InputDecode { what: String, elements: Option<usize>, detail: String },
+    /// A signed blob failed authentication: it was altered, truncated, or
+    /// signed with a different key.
+    BadSignature,
+    /// A blob in one known layout was passed where another was expected, e.g.
+    /// `Follower` records handed to a function that reads username lists.
+    WrongFormat { expected: &'static str, found: &'static str },
+    /// A long-running operation was stopped by its cancellation callback before
+    /// it finished. No partial result is returned.
+    Cancelled,
+}
+
+impl fmt::Display for TrackerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrackerError::UsernameTooLong { length, max, preview } => write!(
+                f,
+                "Username is too long ({} characters, maximum is {}): '{}...'",
+                length, max, preview
+            ),
+            TrackerError::InvalidTimestamp { name, value } => write!(
+                f,
+                "Invalid timestamp for '{}': {} (expected a finite, non-negative number of milliseconds)",
+                name, value
+            ),
+            TrackerError::InvalidDuration { name, value } => write!(
+                f,
+                "Invalid duration for '{}': {} (expected a finite, non-negative number of milliseconds)",
+                name, value
+            ),
+            TrackerError::BlobDecode { what, bytes, detail } => {
+                write!(f, "Failed to deserialize {} ({} bytes): {}", what, bytes, detail)
+            }
+            TrackerError::InputDecode { what, elements: Some(elements), detail } => {
+                write!(f, "Failed to deserialize {} ({} elements): {}", what, elements, detail)
+            }
+            TrackerError::InputDecode { what, elements: None, detail } => {
+                write!(f, "Failed to deserialize {}: {}", what, detail)
+            }
+            TrackerError::BadSignature => {
+                write!(f, "Signature verification failed: the blob was altered or signed with a different key")
+            }
+            TrackerError::WrongFormat { expected, found } => {
+                write!(f, "Wrong blob format: expected {}, found {}", expected, found)
+            }
+            TrackerError::Cancelled => write!(f, "Operation cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for TrackerError {}
+
+impl From<TrackerError> for JsValue {
+    fn from(error: TrackerError) -> Self {
+        JsValue::from_str(&error.to_string())
+    }
+}
+
+/// Checks that every username is within `MAX_USERNAME_CHARS`.
+pub(crate) fn validate_usernames(usernames: &[String]) -> Result<(), TrackerError> {
+    usernames.iter().try_for_each(|username| validate_username(username))
+}
+
+/// Checks that a single username is within `MAX_USERNAME_CHARS`. Used for the
+/// `username` field of record inputs.
+pub(crate) fn validate_username(username: &str) -> Result<(), TrackerError> {
+    const PREVIEW_CHARS: usize = 20;
+
+    let length = username.chars().count();
+    if length > MAX_USERNAME_CHARS {
+        return Err(TrackerError::UsernameTooLong {
+            length,
+            max: MAX_USERNAME_CHARS,
+            preview: username.chars().take(PREVIEW_CHARS).collect(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that a timestamp from JavaScript is a finite, non-negative number of
+/// milliseconds since the Unix epoch.
+pub(crate) fn validate_timestamp(name: &'static str, value: f64) -> Result<(), TrackerError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(TrackerError::InvalidTimestamp { name, value })
+    }
+}
+
+/// Checks that a duration from JavaScript is a finite, non-negative number of
+/// milliseconds.
+pub(crate) fn validate_duration(name: &'static str, value: f64) -> Result<(), TrackerError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(TrackerError::InvalidDuration { name, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_a_10000_char_username_with_a_short_preview() {
+        let usernames = vec!["alice".to_string(), "x".repeat(10_000)];
+        let error = validate_usernames(&usernames).unwrap_err();
+        assert_eq!(
+            error,
+            TrackerError::UsernameTooLong { length: 10_000, max: MAX_USERNAME_CHARS, preview: "x".repeat(20) }
+        );
+        assert!(error.to_string().starts_with("Username is too long (10000 characters, maximum is 100)"));
+    }
+
+    #[test]
+    fn rejects_nan_and_negative_times() {
+        for value in [f64::NAN, -1.0, f64::INFINITY] {
+            assert!(matches!(
+                validate_timestamp("now", value),
+                Err(TrackerError::InvalidTimestamp { name: "now", .. })
+            ));
+            assert!(matches!(
+                validate_duration("grace_ms", value),
+                Err(TrackerError::InvalidDuration { name: "grace_ms", .. })
+            ));
+        }
+        assert_eq!(validate_timestamp("now", 0.0), Ok(()));
+        assert_eq!(validate_duration("grace_ms", 86_400_000.0), Ok(()));
+        assert_eq!(
+            TrackerError::InvalidDuration { name: "window_ms", value: -1.0 }.to_string(),
+            "Invalid duration for 'window_ms': -1 (expected a finite, non-negative number of milliseconds)"
+        );
+    }
+
+    #[test]
+    fn counts_characters_not_bytes() {
+        assert_eq!(validate_username(&"é".repeat(MAX_USERNAME_CHARS)), Ok(()));
+        assert!(validate_username(&"é".repeat(MAX_USERNAME_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn decode_errors_name_the_input_and_its_size() {
+        let what = "old followers".to_string();
+        let blob = TrackerError::BlobDecode { what, bytes: 4123, detail: "eof".to_string() };
+        assert_eq!(blob.to_string(), "Failed to deserialize old followers (4123 bytes): eof");
+
+        let input = |elements| TrackerError::InputDecode {
+            what: "cohort".to_string(),
+            elements,
+            detail: "bad".to_string(),
+        };
+        assert_eq!(input(Some(3)).to_string(), "Failed to deserialize cohort (3 elements): bad");
+        assert_eq!(input(None).to_string(), "Failed to deserialize cohort: bad");
+    }
+}
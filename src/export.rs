@@ -0,0 +1,255 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: export.rs
+* Description: Conversions between stored MessagePack blobs and text formats.
+* These support simple copy-paste workflows where users paste or copy follower
+* lists as text instead of going through the scraper, as well as structured
+* text outputs (such as JSON Patch) for external tooling.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::error::validate_username;
+use crate::follower::decode_follower_records;
+use crate::{decode_followers, encode_mpack, followers_from_js, sorted};
+
+/// A single RFC 6902 JSON Patch operation on the `/followers` array.
+#[derive(Debug, Serialize)]
+pub struct PatchOperation {
+    pub op: &'static str,
+    pub path: String,
+    pub value: String,
+}
+
+/// Parses a newline-separated list of usernames and serializes it to MessagePack.
+///
+/// Each line is trimmed, so both `\n` and `\r\n` line endings are accepted and
+/// stray whitespace is ignored. Blank lines are dropped and duplicates are
+/// removed, keeping the first occurrence.
+///
+/// # Arguments
+///
+/// * `text`: The pasted text, one username per line.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: A byte vector (`Uint8Array` in JS) of the serialized data.
+/// - `Err(JsValue)`: A JavaScript error object if a line is longer than
+///   `MAX_USERNAME_CHARS`, or if serialization fails.
+#[wasm_bindgen]
+pub fn followers_from_text(text: &str) -> Result<Vec<u8>, JsValue> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let followers: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && seen.insert(line))
+        .collect();
+    followers.iter().try_for_each(|username| validate_username(username))?;
+
+    encode_mpack(&followers)
+}
+
+/// Converts a stored follower blob into newline-separated text.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing a follower list serialized in
+///   MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(String)`: The usernames joined by `\n`, in stored order.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn followers_to_text(mpack: &[u8]) -> Result<String, JsValue> {
+    Ok(decode_followers(mpack, "followers")?.join("\n"))
+}
+
+/// Expresses the follower diff as an RFC 6902 JSON Patch document.
+///
+/// The patch applies to a document of the form `{ "followers": [...] }` whose
+/// array holds the old follower list in stored order. All `remove` operations
+/// come first, addressed by index in descending order so that each removal
+/// leaves the remaining indices valid. Gained followers are then appended with
+/// `add` operations on `/followers/-`, sorted by username.
+///
+/// Every operation carries a `value` with the affected username. RFC 6902
+/// requires appliers to ignore it on `remove`, but it keeps the patch readable.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(String)`: The JSON Patch document as a JSON array string.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or encoding fails.
+#[wasm_bindgen]
+pub fn diff_as_json_patch(new_followers_js: JsValue, old_followers_mpack: &[u8]) -> Result<String, JsValue> {
+    let old_followers = decode_followers(old_followers_mpack, "old followers")?;
+    let new_set: HashSet<String> = followers_from_js(new_followers_js, "new followers")?.into_iter().collect();
+    let old_set: HashSet<&str> = old_followers.iter().map(String::as_str).collect();
+
+    let mut operations: Vec<PatchOperation> = old_followers
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, username)| !new_set.contains(*username))
+        .map(|(index, username)| PatchOperation {
+            op: "remove",
+            path: format!("/followers/{}", index),
+            value: username.clone(),
+        })
+        .collect();
+
+    let gained = sorted(new_set.into_iter().filter(|u| !old_set.contains(u.as_str())).collect());
+    operations.extend(gained.into_iter().map(|username| PatchOperation {
+        op: "add",
+        path: "/followers/-".to_string(),
+        value: username,
+    }));
+
+    serde_json::to_string(&operations)
+        .map_err(|e| JsValue::from_str(&format!("Failed to encode JSON Patch: {}", e)))
+}
+
+/// Encodes a MessagePack blob as URL-safe Base64 without padding.
+///
+/// The output only uses `A-Z`, `a-z`, `0-9`, `-` and `_`, so it can be placed in
+/// URL parameters or stored by sync backends that only accept text.
+///
+/// # Arguments
+///
+/// * `mpack`: The blob to encode.
+///
+/// # Returns
+///
+/// The Base64 text.
+#[wasm_bindgen]
+pub fn mpack_to_base64(mpack: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(mpack)
+}
+
+/// Decodes URL-safe, unpadded Base64 text produced by `mpack_to_base64`.
+///
+/// # Arguments
+///
+/// * `s`: The Base64 text.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The decoded blob (`Uint8Array` in JS).
+/// - `Err(JsValue)`: A JavaScript error object if the text is not valid
+///   URL-safe Base64 without padding.
+#[wasm_bindgen]
+pub fn base64_to_mpack(s: &str) -> Result<Vec<u8>, JsValue> {
+    URL_SAFE_NO_PAD
+        .decode(s)
+        .map_err(|e| JsValue::from_str(&format!("Failed to decode Base64: {}", e)))
+}
+
+/// Exports stored `Follower` records as a spreadsheet-ready CSV document.
+///
+/// The output starts with a `user_id,username,full_name,is_verified` header
+/// and has one row per record, each terminated by `\n`. Fields are quoted
+/// following RFC 4180 when they contain a comma, a quote or a line break, with
+/// embedded quotes doubled. Missing optional fields are left blank.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing follower records, as produced by
+///   `serialize_follower_records_to_mpack`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(String)`: The CSV document.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn followers_to_csv_full(mpack: &[u8]) -> Result<String, JsValue> {
+    let followers = decode_follower_records(mpack, "follower records")?;
+
+    let mut csv = String::from("user_id,username,full_name,is_verified\n");
+    for follower in &followers {
+        let is_verified = follower.is_verified.map(|v| v.to_string()).unwrap_or_default();
+        let row = [
+            follower.user_id.to_string(),
+            csv_field(&follower.username),
+            csv_field(follower.full_name.as_deref().unwrap_or("")),
+            is_verified,
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    Ok(csv)
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::follower::Follower;
+
+    fn decode(blob: &[u8]) -> Vec<String> {
+        rmp_serde::from_slice(blob).unwrap()
+    }
+
+    #[test]
+    fn text_import_accepts_crlf_and_skips_blank_lines() {
+        let blob = followers_from_text("alice\r\n\r\n  bob  \r\n\ncarol\r\nalice\n").unwrap();
+        assert_eq!(decode(&blob), ["alice", "bob", "carol"]);
+    }
+
+    #[test]
+    fn text_export_round_trips_the_import() {
+        let blob = followers_from_text("alice\nbob\n").unwrap();
+        assert_eq!(followers_to_text(&blob).unwrap(), "alice\nbob");
+        assert_eq!(followers_to_text(&[]).unwrap(), "");
+    }
+
+    #[test]
+    fn base64_round_trips_a_blob_with_high_bytes() {
+        let blob: Vec<u8> = vec![0x00, 0x7f, 0x80, 0xfb, 0xfe, 0xff, 0x92, 0xa5];
+        let text = mpack_to_base64(&blob);
+        assert_eq!(text, "AH-A-_7_kqU");
+        assert!(text.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(base64_to_mpack(&text).unwrap(), blob);
+    }
+
+    #[test]
+    fn csv_quotes_a_full_name_with_a_comma_and_a_quote() {
+        let followers = vec![
+            Follower {
+                username: "alice".to_string(),
+                user_id: 42,
+                full_name: Some("Alice \"Al\" Smith, PhD".to_string()),
+                is_verified: Some(true),
+            },
+            Follower { username: "bob".to_string(), user_id: 7, full_name: None, is_verified: None },
+        ];
+        let csv = followers_to_csv_full(&rmp_serde::to_vec(&followers).unwrap()).unwrap();
+        assert_eq!(
+            csv,
+            "user_id,username,full_name,is_verified\n42,alice,\"Alice \"\"Al\"\" Smith, PhD\",true\n7,bob,,\n"
+        );
+    }
+}
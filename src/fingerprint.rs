@@ -0,0 +1,323 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: fingerprint.rs
+* Description: Content fingerprints for follower lists. A fingerprint is a
+* short hex digest that identifies the *set* of followers: the list is
+* de-duplicated and sorted before hashing, so two scrapes that found the same
+* accounts in a different order produce the same digest. Callers can choose
+* between fast non-cryptographic hashes (FNV-1a, xxHash) for cheap change
+* detection and SHA-256 when collision resistance matters. A separate
+* commutative digest needs no sorting, so an incremental `FingerprintState`
+* can fold in one page at a time.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashSet};
+use xxhash_rust::xxh64::{xxh64, Xxh64};
+
+use crate::{decode_followers, followers_from_js};
+
+/// The hash functions a fingerprint can be computed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FingerprintAlgo {
+    /// 64-bit FNV-1a. Fast and dependency-free; the default.
+    Fnv,
+    /// 64-bit xxHash (XXH64, seed 0). Faster than FNV on long inputs.
+    XxHash,
+    /// SHA-256. Slower, but suitable for integrity checks and sharing.
+    Sha256,
+    /// A 128-bit commutative digest: the wrapping sum of the FNV-1a hashes of the
+    /// unique usernames, followed by the XOR of their seeded xxHash hashes. It can
+    /// be built up one username at a time, which `FingerprintState` relies on.
+    /// Versioned, so a future combiner gets a new name instead of new digests.
+    SumV1,
+}
+
+impl FingerprintAlgo {
+    /// Parses an algorithm name as accepted from JavaScript.
+    pub fn parse(name: &str) -> Result<Self, JsValue> {
+        match name {
+            "fnv" => Ok(FingerprintAlgo::Fnv),
+            "xxhash" => Ok(FingerprintAlgo::XxHash),
+            "sha256" => Ok(FingerprintAlgo::Sha256),
+            "sum-v1" => Ok(FingerprintAlgo::SumV1),
+            other => Err(JsValue::from_str(&format!(
+                "Unknown fingerprint algorithm '{}': expected \"fnv\", \"xxhash\", \"sha256\" or \"sum-v1\"",
+                other
+            ))),
+        }
+    }
+}
+
+/// Computes the default (FNV-1a) fingerprint of a follower list.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(String)`: A lowercase hex digest that is independent of list order
+///   and duplicates.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid.
+#[wasm_bindgen]
+pub fn follower_list_fingerprint(followers_js: JsValue) -> Result<String, JsValue> {
+    follower_list_fingerprint_algo(followers_js, "fnv")
+}
+
+/// Computes the fingerprint of a follower list with a chosen hash function.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+/// * `algo`: One of `"fnv"`, `"xxhash"`, `"sha256"` or `"sum-v1"`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(String)`: A lowercase hex digest that is independent of list order
+///   and duplicates.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid or the
+///   algorithm name is unknown.
+#[wasm_bindgen]
+pub fn follower_list_fingerprint_algo(followers_js: JsValue, algo: &str) -> Result<String, JsValue> {
+    let algo = FingerprintAlgo::parse(algo)?;
+    let followers = followers_from_js(followers_js, "followers")?;
+    Ok(fingerprint(followers.iter().map(String::as_str), algo))
+}
+
+/// An incremental follower list fingerprint, for lists that arrive page by page.
+///
+/// The extension can feed each scraped page to `update` as it arrives instead
+/// of re-fingerprinting the whole list on every page. Each page costs time
+/// proportional to its own length, and `finalize` is constant time: the state
+/// keeps the usernames seen so far, to skip repeats, and the running `"sum-v1"`
+/// digest. Like the one-shot fingerprint, the digest is independent of the
+/// order and duplication of the usernames, so `finalize` returns exactly what
+/// `follower_list_fingerprint_algo` returns for the full list with `"sum-v1"`.
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct FingerprintState {
+    seen: HashSet<String>,
+    digest: SumDigest,
+}
+
+#[wasm_bindgen]
+impl FingerprintState {
+    /// Creates a state that has seen no usernames.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> FingerprintState {
+        FingerprintState::default()
+    }
+
+    /// Adds a page of usernames to the fingerprint.
+    ///
+    /// # Arguments
+    ///
+    /// * `usernames_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(())`: The usernames were added.
+    /// - `Err(JsValue)`: A JavaScript error object if the input is invalid. The state
+    ///   is left unchanged.
+    pub fn update(&mut self, usernames_js: JsValue) -> Result<(), JsValue> {
+        let usernames = followers_from_js(usernames_js, "usernames")?;
+        self.add(&usernames);
+        Ok(())
+    }
+
+    /// Returns the `"sum-v1"` fingerprint of every username added so far. The
+    /// state is not consumed, so more pages can be added afterwards.
+    pub fn finalize(&self) -> String {
+        self.digest.to_hex()
+    }
+}
+
+impl FingerprintState {
+    /// Folds a page of already validated usernames into the running digest.
+    fn add(&mut self, usernames: &[String]) {
+        for username in usernames {
+            if !self.seen.contains(username) {
+                self.digest.add(username);
+                self.seen.insert(username.clone());
+            }
+        }
+    }
+}
+
+/// Decides whether a poll found any change, without running a full diff.
+///
+/// The stored follower list is fingerprinted and compared against the
+/// fingerprint of the new scrape, which the caller computes with
+/// `follower_list_fingerprint`. High-frequency pollers that mostly see no
+/// change can call this first and skip the diff whenever it returns `false`.
+///
+/// # Arguments
+///
+/// * `new_fingerprint`: The default (FNV-1a) fingerprint of the latest follower list.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(bool)`: `true` if the fingerprints differ, i.e. the follower set changed.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn quick_check(new_fingerprint: &str, old_followers_mpack: &[u8]) -> Result<bool, JsValue> {
+    let old_followers = decode_followers(old_followers_mpack, "old followers")?;
+    let old_fingerprint = fingerprint(old_followers.iter().map(String::as_str), FingerprintAlgo::Fnv);
+    Ok(!old_fingerprint.eq_ignore_ascii_case(new_fingerprint.trim()))
+}
+
+/// Computes the fingerprint of a set of usernames.
+///
+/// For FNV-1a, xxHash and SHA-256, the usernames are de-duplicated and sorted,
+/// then each one is fed to the hash followed by a `\n` terminator so that
+/// `["ab", "c"]` and `["a", "bc"]` hash differently. For `"sum-v1"`, each
+/// unique username is hashed on its own and folded into a `SumDigest`.
+pub(crate) fn fingerprint<'a>(usernames: impl IntoIterator<Item = &'a str>, algo: FingerprintAlgo) -> String {
+    let canonical: BTreeSet<&str> = usernames.into_iter().collect();
+
+    match algo {
+        FingerprintAlgo::Fnv => {
+            let mut hash = FNV_OFFSET_BASIS;
+            for username in &canonical {
+                hash = fnv1a_64(hash, username.as_bytes());
+                hash = fnv1a_64(hash, b"\n");
+            }
+            format!("{:016x}", hash)
+        }
+        FingerprintAlgo::XxHash => {
+            let mut hasher = Xxh64::new(0);
+            for username in &canonical {
+                hasher.update(username.as_bytes());
+                hasher.update(b"\n");
+            }
+            format!("{:016x}", hasher.digest())
+        }
+        FingerprintAlgo::Sha256 => {
+            let mut hasher = Sha256::new();
+            for username in &canonical {
+                hasher.update(username.as_bytes());
+                hasher.update(b"\n");
+            }
+            hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+        }
+        FingerprintAlgo::SumV1 => {
+            let mut digest = SumDigest::default();
+            for username in &canonical {
+                digest.add(username);
+            }
+            digest.to_hex()
+        }
+    }
+}
+
+/// The seed of the xxHash half of `SumDigest`, so it is independent of the
+/// unseeded `"xxhash"` fingerprint.
+const SUM_V1_XXH_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The running `"sum-v1"` digest. Both halves are commutative, so usernames can
+/// be added in any order, but each unique username must be added only once.
+/// A collision has to cancel out under addition and XOR of two unrelated
+/// hashes at the same time, which a plain sum alone would not require.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct SumDigest {
+    sum: u64,
+    xor: u64,
+}
+
+impl SumDigest {
+    fn add(&mut self, username: &str) {
+        self.sum = self.sum.wrapping_add(fnv1a_64(FNV_OFFSET_BASIS, username.as_bytes()));
+        self.xor ^= xxh64(username.as_bytes(), SUM_V1_XXH_SEED);
+    }
+
+    fn to_hex(self) -> String {
+        format!("{:016x}{:016x}", self.sum, self.xor)
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Continues a 64-bit FNV-1a hash over `bytes`.
+fn fnv1a_64(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn state_fed_in_two_chunks_matches_one_shot_digest() {
+        let followers = names(&["alice", "bob", "carol", "dave", "erin"]);
+        let one_shot = fingerprint(followers.iter().map(String::as_str), FingerprintAlgo::SumV1);
+
+        let mut state = FingerprintState::new();
+        state.add(&followers[..2]);
+        state.add(&followers[2..]);
+        assert_eq!(state.finalize(), one_shot);
+    }
+
+    #[test]
+    fn state_ignores_usernames_repeated_across_chunks() {
+        let mut state = FingerprintState::new();
+        state.add(&names(&["alice", "bob"]));
+        state.add(&names(&["bob", "carol"]));
+        assert_eq!(state.finalize(), fingerprint(["alice", "bob", "carol"], FingerprintAlgo::SumV1));
+    }
+
+    const ALGOS: [FingerprintAlgo; 4] =
+        [FingerprintAlgo::Fnv, FingerprintAlgo::XxHash, FingerprintAlgo::Sha256, FingerprintAlgo::SumV1];
+
+    #[test]
+    fn every_algorithm_ignores_order_and_duplicates() {
+        for algo in ALGOS {
+            let digest = fingerprint(["alice", "bob", "carol"], algo);
+            assert_eq!(fingerprint(["carol", "alice", "bob", "alice"], algo), digest, "{:?}", algo);
+            assert_ne!(fingerprint(["alice", "bob"], algo), digest, "{:?}", algo);
+        }
+    }
+
+    #[test]
+    fn digests_are_stable_across_builds() {
+        assert_eq!(fingerprint(["a"], FingerprintAlgo::Fnv), "089bdc07b544e7b2");
+        assert_eq!(fingerprint(["a"], FingerprintAlgo::XxHash), "fbbde8981eccc855");
+        assert_eq!(
+            fingerprint(["a"], FingerprintAlgo::Sha256),
+            "87428fc522803d31065e7bce3cf03fe475096631e5e07bbd7a0fde60c4cf25c7"
+        );
+        assert_eq!(fingerprint(["a"], FingerprintAlgo::SumV1), "af63dc4c8601ec8c9a7c6d2ea45568c9");
+    }
+
+    #[test]
+    fn parses_each_algorithm_name() {
+        assert_eq!(FingerprintAlgo::parse("fnv"), Ok(FingerprintAlgo::Fnv));
+        assert_eq!(FingerprintAlgo::parse("xxhash"), Ok(FingerprintAlgo::XxHash));
+        assert_eq!(FingerprintAlgo::parse("sha256"), Ok(FingerprintAlgo::Sha256));
+        assert_eq!(FingerprintAlgo::parse("sum-v1"), Ok(FingerprintAlgo::SumV1));
+    }
+
+    #[test]
+    fn quick_check_reports_whether_the_fingerprint_changed() {
+        let stored = rmp_serde::to_vec(&["alice", "bob", "carol"]).unwrap();
+        let same = fingerprint(["carol", "alice", "bob"], FingerprintAlgo::Fnv);
+        let changed = fingerprint(["alice", "bob"], FingerprintAlgo::Fnv);
+
+        assert!(!quick_check(&same, &stored).unwrap());
+        assert!(!quick_check(&format!(" {} ", same.to_uppercase()), &stored).unwrap());
+        assert!(quick_check(&changed, &stored).unwrap());
+    }
+}
@@ -0,0 +1,319 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: follower.rs
+* Description: Support for full follower records. Plain snapshots only store
+* usernames, which change when an account renames itself and can be reused
+* once an account is deleted. A `Follower` record pairs the username with the
+* account's numeric `user_id`, which never changes, so snapshots built from
+* records can detect identity changes that a username list cannot.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::error::validate_username;
+use crate::format::{decode_layout, BlobFormat};
+use crate::{decode_followers, decode_records, encode_mpack, followers_from_js, records_from_js, sorted, to_js};
+
+/// A single follower as captured by the scraper.
+///
+/// `full_name` and `is_verified` are optional profile metadata. Records
+/// written before these fields existed still decode, with both left empty.
+///
+/// # Encoding
+///
+/// `rmp_serde` encodes each record as a MessagePack array of its fields in
+/// declaration order, `[username, user_id, full_name, is_verified]`, not as a
+/// map. The `user_id` uses the smallest MessagePack integer type that fits the
+/// value (positive fixint, then `uint 8`/`16`/`32`/`64`), and multi-byte
+/// integers are always written in big-endian (network) order as required by
+/// the MessagePack specification. For example,
+/// `{ username: "a", user_id: 300 }` inside a one-element list is stored as:
+///
+/// ```text
+/// 91          array of 1 record
+/// 94          record: array of 4 fields
+/// a1 61       username: fixstr "a"
+/// cd 01 2c    user_id: uint 16, 300 in big-endian
+/// c0          full_name: nil
+/// c0          is_verified: nil
+/// ```
+///
+/// The byte layout is therefore independent of the host's endianness, so blobs
+/// written by the little-endian WASM build can be read by any native build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Follower {
+    pub username: String,
+    pub user_id: u64,
+    #[serde(default)]
+    pub full_name: Option<String>,
+    #[serde(default)]
+    pub is_verified: Option<bool>,
+}
+
+/// A follower paired with that account's own follower count, used to rank
+/// losses by how influential the departed account is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FollowerWithCount {
+    pub username: String,
+    pub followers: u64,
+}
+
+/// A follower as captured by the scraper together with how certain it was
+/// about the capture, from `0.0` (guess) to `1.0` (certain).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoredFollower {
+    pub username: String,
+    pub confidence: f64,
+}
+
+/// A username whose owning account changed between two snapshots.
+#[derive(Debug, Serialize)]
+pub struct HandleTakeover {
+    pub username: String,
+    pub old_user_id: u64,
+    pub new_user_id: u64,
+}
+
+/// Serializes a list of follower records into the MessagePack binary format.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `{ username, user_id }` objects.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: A byte vector (`Uint8Array` in JS) of the serialized data.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid or
+///   serialization fails.
+#[wasm_bindgen]
+pub fn serialize_follower_records_to_mpack(followers_js: JsValue) -> Result<Vec<u8>, JsValue> {
+    let followers: Vec<Follower> = records_from_js(followers_js, "follower records")?;
+    followers.iter().try_for_each(|f| validate_username(&f.username))?;
+    encode_mpack(&followers)
+}
+
+/// Finds usernames that exist in both snapshots but belong to a different
+/// account in each.
+///
+/// When an account is deleted, Instagram eventually releases its handle and
+/// another account can claim it. The username then looks like a continuing
+/// follower, but the `user_id` behind it is new. This is a strong signal of
+/// handle reuse or impersonation and is reported here instead of being
+/// silently treated as "still following".
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `{ username, user_id }` objects representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   records, as produced by `serialize_follower_records_to_mpack`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of `{ username, old_user_id, new_user_id }`
+///   objects, sorted by username.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_handle_takeovers(new_followers_js: JsValue, old_followers_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let old_followers = decode_follower_records(old_followers_mpack, "old follower records")?;
+    let new_followers: Vec<Follower> = records_from_js(new_followers_js, "new follower records")?;
+    new_followers.iter().try_for_each(|f| validate_username(&f.username))?;
+
+    let old_ids: HashMap<String, u64> = old_followers
+        .into_iter()
+        .map(|f| (f.username, f.user_id))
+        .collect();
+
+    let mut takeovers: Vec<HandleTakeover> = new_followers
+        .into_iter()
+        .filter_map(|f| match old_ids.get(&f.username) {
+            Some(&old_user_id) if old_user_id != f.user_id => Some(HandleTakeover {
+                username: f.username,
+                old_user_id,
+                new_user_id: f.user_id,
+            }),
+            _ => None,
+        })
+        .collect();
+    takeovers.sort_by(|a, b| a.username.cmp(&b.username));
+    takeovers.dedup_by(|a, b| a.username == b.username);
+
+    to_js(&takeovers)
+}
+
+/// Finds unfollowers and ranks them by their own follower count.
+///
+/// Losing an account with a large audience is usually more notable than losing
+/// a small one. The caller supplies the previous followers together with each
+/// account's follower count; unfollowers are returned with the largest
+/// accounts first. Ties are broken alphabetically so the order is stable.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_with_counts_js`: A `JsValue` from JavaScript, expected to be an array
+///   of `{ username, followers }` objects describing the previous followers.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of `{ username, followers }` objects for the
+///   unfollowers, sorted by `followers` in descending order.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_ranked(new_followers_js: JsValue, old_followers_with_counts_js: JsValue) -> Result<JsValue, JsValue> {
+    let new_followers = followers_from_js(new_followers_js, "new followers")?;
+    let old_followers: Vec<FollowerWithCount> =
+        records_from_js(old_followers_with_counts_js, "old followers with counts")?;
+    old_followers.iter().try_for_each(|f| validate_username(&f.username))?;
+
+    let new_set: HashSet<String> = new_followers.into_iter().collect();
+    let mut seen: HashSet<String> = HashSet::with_capacity(old_followers.len());
+    let mut ranked: Vec<FollowerWithCount> = old_followers
+        .into_iter()
+        .filter(|f| !new_set.contains(&f.username) && seen.insert(f.username.clone()))
+        .collect();
+    ranked.sort_by(|a, b| b.followers.cmp(&a.followers).then_with(|| a.username.cmp(&b.username)));
+
+    to_js(&ranked)
+}
+
+/// Serializes a list of scored follower captures into the MessagePack binary format.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `{ username, confidence }` objects.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: A byte vector (`Uint8Array` in JS) of the serialized data.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid or
+///   serialization fails.
+#[wasm_bindgen]
+pub fn serialize_followers_scored(followers_js: JsValue) -> Result<Vec<u8>, JsValue> {
+    let followers: Vec<ScoredFollower> = records_from_js(followers_js, "scored followers")?;
+    followers.iter().try_for_each(|f| validate_username(&f.username))?;
+    encode_mpack(&followers)
+}
+
+/// Finds unfollowers while ignoring captures the scraper was unsure about.
+///
+/// Entries of the new capture with a confidence below `min_confidence` are
+/// ignored when deciding absences: only confident sightings count as present.
+/// Every account of the previous capture that is missing from the confident
+/// entries is reported, whatever confidence it was captured with back then.
+///
+/// # Arguments
+///
+/// * `new_scored_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `{ username, confidence }` objects representing the latest capture.
+/// * `old_mpack`: A byte slice (`&[u8]`) containing the previous capture, as produced
+///   by `serialize_followers_scored`.
+/// * `min_confidence`: The lowest confidence of a new entry that is trusted as a
+///   sighting of the account.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the unfollowers.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_min_confidence(
+    new_scored_js: JsValue,
+    old_mpack: &[u8],
+    min_confidence: f64,
+) -> Result<JsValue, JsValue> {
+    let old_followers: Vec<ScoredFollower> = decode_records(old_mpack, "old scored followers")?;
+    let new_followers: Vec<ScoredFollower> = records_from_js(new_scored_js, "new scored followers")?;
+    new_followers.iter().try_for_each(|f| validate_username(&f.username))?;
+
+    let present: HashSet<String> = new_followers
+        .into_iter()
+        .filter(|f| f.confidence >= min_confidence)
+        .map(|f| f.username)
+        .collect();
+    let unfollowers: HashSet<String> = old_followers
+        .into_iter()
+        .map(|f| f.username)
+        .filter(|username| !present.contains(username))
+        .collect();
+
+    to_js(&sorted(unfollowers.into_iter().collect()))
+}
+
+/// Finds unfollowers when the previous snapshot is a plain username list and the
+/// new scrape already yields full `Follower` records.
+///
+/// This bridges the migration from username-keyed to `user_id`-keyed storage:
+/// the new records are matched against the old usernames by their `username`
+/// field, so no check is lost while the stored blob is still the old layout.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `Follower` objects representing the latest list of followers.
+/// * `old_usernames_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list as usernames, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the departed usernames.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_mixed(new_followers_js: JsValue, old_usernames_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let old_followers = decode_followers(old_usernames_mpack, "old followers")?;
+    let new_followers: Vec<Follower> = records_from_js(new_followers_js, "new follower records")?;
+    new_followers.iter().try_for_each(|f| validate_username(&f.username))?;
+
+    let present: HashSet<String> = new_followers.into_iter().map(|f| f.username).collect();
+    let unfollowers: HashSet<String> = old_followers.into_iter().filter(|u| !present.contains(u)).collect();
+
+    to_js(&sorted(unfollowers.into_iter().collect()))
+}
+
+/// Deserializes a blob of `Follower` records, reporting a clear
+/// `TrackerError::WrongFormat` when handed a plain username list instead.
+pub(crate) fn decode_follower_records(mpack: &[u8], what: &str) -> Result<Vec<Follower>, JsValue> {
+    decode_layout(mpack, what, BlobFormat::FollowerRecords)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(username: &str, user_id: u64) -> Follower {
+        Follower { username: username.to_string(), user_id, full_name: None, is_verified: None }
+    }
+
+    #[test]
+    fn user_id_is_encoded_big_endian_in_the_smallest_type() {
+        let bytes = rmp_serde::to_vec(&vec![record("a", 300)]).unwrap();
+        assert_eq!(bytes, [0x91, 0x94, 0xa1, 0x61, 0xcd, 0x01, 0x2c, 0xc0, 0xc0]);
+
+        let bytes = rmp_serde::to_vec(&record("a", 0x0102_0304_0506_0708)).unwrap();
+        assert_eq!(bytes, [0x94, 0xa1, 0x61, 0xcf, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xc0, 0xc0]);
+    }
+
+    #[test]
+    fn large_ids_round_trip_exactly() {
+        let followers = vec![record("a", 7), record("b", u32::MAX as u64 + 1), record("c", u64::MAX)];
+        let decoded: Vec<Follower> = rmp_serde::from_slice(&rmp_serde::to_vec(&followers).unwrap()).unwrap();
+        assert_eq!(decoded, followers);
+    }
+
+    #[test]
+    fn two_field_records_from_before_the_metadata_fields_still_decode() {
+        // `[["a", 300]]`, as written when a record only had a username and an id.
+        let legacy = [0x91, 0x92, 0xa1, 0x61, 0xcd, 0x01, 0x2c];
+        let decoded: Vec<Follower> = rmp_serde::from_slice(&legacy).unwrap();
+        assert_eq!(decoded, [record("a", 300)]);
+    }
+}
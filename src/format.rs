@@ -0,0 +1,464 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: format.rs
+* Description: Detection of the different follower blob layouts the crate
+* writes. A stored blob may hold a plain list of usernames or a list of full
+* `Follower` records; both describe a set of followers. The helpers here
+* identify which layout a blob uses from its leading bytes and decode it to the
+* plain username set, so callers can compare blobs without knowing how each was
+* written, and turn a blob of the wrong layout (including a compressed or a
+* signed blob, which must be unwrapped first) into a clear error instead of a
+* serde message. Chunks written by `chunk_mpack` are complete username lists
+* and are recognized as such.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::de::{DeserializeOwned, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::error::TrackerError;
+use crate::follower::{decode_follower_records, Follower};
+use crate::signing::TAG_LEN;
+use crate::{sorted, to_js};
+
+/// The blob layouts that can be told apart by their leading bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobFormat {
+    /// A MessagePack array of usernames, as written by
+    /// `serialize_followers_to_mpack`, or a map keyed by usernames.
+    Usernames,
+    /// A MessagePack array of `Follower` records, as written by
+    /// `serialize_follower_records_to_mpack`.
+    FollowerRecords,
+    /// A zlib or gzip stream, recognized by its magic bytes. The crate never
+    /// writes these; the caller has to decompress them first.
+    Compressed,
+    /// A blob followed by a 32-byte tag, as written by `sign_mpack`. It has to go
+    /// through `verify_signed_mpack` first, which also strips the tag.
+    Signed,
+}
+
+/// A username list read tolerantly: either a MessagePack array of usernames,
+/// which is what this crate writes, or a map whose keys are the usernames, as
+/// some external tools store them. Map values are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct UsernameList(pub Vec<String>);
+
+impl<'de> Deserialize<'de> for UsernameList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct UsernameListVisitor;
+
+        impl<'de> Visitor<'de> for UsernameListVisitor {
+            type Value = UsernameList;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an array of usernames or a map keyed by username")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut usernames = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(username) = seq.next_element()? {
+                    usernames.push(username);
+                }
+                Ok(UsernameList(usernames))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut usernames = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((username, IgnoredAny)) = map.next_entry()? {
+                    usernames.push(username);
+                }
+                Ok(UsernameList(usernames))
+            }
+        }
+
+        deserializer.deserialize_any(UsernameListVisitor)
+    }
+}
+
+impl BlobFormat {
+    /// A human-readable name for error messages.
+    pub fn name(self) -> &'static str {
+        match self {
+            BlobFormat::Usernames => "username list",
+            BlobFormat::FollowerRecords => "follower records",
+            BlobFormat::Compressed => "compressed blob",
+            BlobFormat::Signed => "signed blob",
+        }
+    }
+}
+
+/// Identifies the layout of a non-empty blob from its leading bytes, or `None`
+/// if it is not a blob this crate knows.
+///
+/// Nothing is decoded. Compressed blobs are told apart by their magic bytes,
+/// which are MessagePack integers and so can never start a follower list. For
+/// a follower list, the marker of its first element tells usernames (strings)
+/// from records (arrays or maps), and the MessagePack markers are walked to the
+/// end of the value to find the tag of a signed blob.
+pub(crate) fn detect_format(mpack: &[u8]) -> Option<BlobFormat> {
+    if is_compressed(mpack) {
+        return Some(BlobFormat::Compressed);
+    }
+    let layout = match *mpack.first()? {
+        0x80..=0x8f | 0xde | 0xdf => BlobFormat::Usernames,
+        marker @ (0x90..=0x9f | 0xdc | 0xdd) => {
+            let first_element = match marker {
+                0xdc => 3,
+                0xdd => 5,
+                _ => 1,
+            };
+            match mpack.get(first_element) {
+                // An empty list (or a truncated one) reads as usernames.
+                None => BlobFormat::Usernames,
+                Some(0xa0..=0xbf | 0xd9..=0xdb) => BlobFormat::Usernames,
+                Some(0x80..=0x9f | 0xdc..=0xdf) => BlobFormat::FollowerRecords,
+                Some(_) => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    match value_len(mpack)? {
+        len if len == mpack.len() => Some(layout),
+        len if len + TAG_LEN == mpack.len() => Some(BlobFormat::Signed),
+        _ => None,
+    }
+}
+
+/// Whether `bytes` start with a zlib header (RFC 1950: DEFLATE with a valid
+/// header checksum) or the gzip magic number.
+fn is_compressed(bytes: &[u8]) -> bool {
+    match bytes {
+        [0x1f, 0x8b, ..] => true,
+        &[cmf, flg, ..] => cmf & 0x0f == 8 && cmf >> 4 <= 7 && (u16::from(cmf) << 8 | u16::from(flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+/// Returns the length in bytes of the MessagePack value at the start of
+/// `bytes` by walking its markers, without decoding anything. `None` if the
+/// bytes end before the value does or hold an invalid marker.
+fn value_len(bytes: &[u8]) -> Option<usize> {
+    let read_len = |pos: usize, width: usize| -> Option<usize> {
+        let field = bytes.get(pos..pos + width)?;
+        Some(field.iter().fold(0, |len, &byte| len << 8 | byte as usize))
+    };
+
+    let (mut pos, mut pending) = (0usize, 1usize);
+    while pending > 0 {
+        pending -= 1;
+        let marker = *bytes.get(pos)?;
+        pos += 1;
+        // The bytes to skip after the marker, and the number of nested values.
+        let (skip, nested) = match marker {
+            0x00..=0x7f | 0xc0 | 0xc2 | 0xc3 | 0xe0..=0xff => (0, 0),
+            0x80..=0x8f => (0, 2 * (marker & 0x0f) as usize),
+            0x90..=0x9f => (0, (marker & 0x0f) as usize),
+            0xa0..=0xbf => ((marker & 0x1f) as usize, 0),
+            0xc4 | 0xd9 => (1 + read_len(pos, 1)?, 0),
+            0xc5 | 0xda => (2 + read_len(pos, 2)?, 0),
+            0xc6 | 0xdb => (4 + read_len(pos, 4)?, 0),
+            0xc7 => (2 + read_len(pos, 1)?, 0),
+            0xc8 => (3 + read_len(pos, 2)?, 0),
+            0xc9 => (5 + read_len(pos, 4)?, 0),
+            0xcc | 0xd0 => (1, 0),
+            0xcd | 0xd1 => (2, 0),
+            0xca | 0xce | 0xd2 => (4, 0),
+            0xcb | 0xcf | 0xd3 => (8, 0),
+            0xd4..=0xd8 => (1 + (1 << (marker - 0xd4)), 0),
+            0xdc => (2, read_len(pos, 2)?),
+            0xdd => (4, read_len(pos, 4)?),
+            0xde => (2, 2 * read_len(pos, 2)?),
+            0xdf => (4, 2 * read_len(pos, 4)?),
+            0xc1 => return None,
+        };
+        pos = pos.checked_add(skip)?;
+        pending = pending.checked_add(nested)?;
+    }
+    (pos <= bytes.len()).then_some(pos)
+}
+
+/// Deserializes a follower blob that is expected to be in the `expected`
+/// layout, treating an empty slice as the type's default. A blob of another
+/// known layout is reported as a `TrackerError::WrongFormat`, and so is one
+/// with bytes left over after the list, such as a signed blob.
+pub(crate) fn decode_layout<T: DeserializeOwned + Default>(
+    mpack: &[u8],
+    what: &str,
+    expected: BlobFormat,
+) -> Result<T, JsValue> {
+    if mpack.is_empty() {
+        return Ok(T::default());
+    }
+    read_layout(mpack, what).map_err(|e| wrong_format_or(mpack, expected, e.into()))
+}
+
+/// Deserializes exactly one MessagePack value spanning all of `mpack`.
+fn read_layout<T: DeserializeOwned>(mpack: &[u8], what: &str) -> Result<T, TrackerError> {
+    let mut rest = mpack;
+    let value = rmp_serde::from_read(&mut rest).map_err(|e| e.to_string());
+    let value = value.and_then(|value| match rest.len() {
+        0 => Ok(value),
+        extra => Err(format!("{} unexpected bytes after the end of the list", extra)),
+    });
+    value.map_err(|detail| TrackerError::BlobDecode { what: what.to_string(), bytes: mpack.len(), detail })
+}
+
+/// Builds the error for a blob that failed to decode as `expected`. When the
+/// blob is recognizably in another layout, a `TrackerError::WrongFormat` is
+/// reported; otherwise `decode_error` is returned unchanged.
+pub(crate) fn wrong_format_or(mpack: &[u8], expected: BlobFormat, decode_error: JsValue) -> JsValue {
+    match detect_format(mpack) {
+        Some(found) if found != expected => {
+            TrackerError::WrongFormat { expected: expected.name(), found: found.name() }.into()
+        }
+        _ => decode_error,
+    }
+}
+
+/// Decodes a follower blob of any supported layout into its usernames,
+/// returning the detected layout alongside. An empty slice is an empty
+/// username list.
+pub(crate) fn decode_any_followers(mpack: &[u8], what: &str) -> Result<(BlobFormat, Vec<String>), JsValue> {
+    read_any_followers(mpack, what).map_err(JsValue::from)
+}
+
+/// The fallible core of `decode_any_followers`, returning the `TrackerError`
+/// itself so callers can report it without throwing.
+fn read_any_followers(mpack: &[u8], what: &str) -> Result<(BlobFormat, Vec<String>), TrackerError> {
+    if mpack.is_empty() {
+        return Ok((BlobFormat::Usernames, Vec::new()));
+    }
+    match detect_format(mpack) {
+        Some(BlobFormat::Usernames) => {
+            let UsernameList(usernames) = read_layout(mpack, what)?;
+            Ok((BlobFormat::Usernames, usernames))
+        }
+        Some(BlobFormat::FollowerRecords) => {
+            let records: Vec<Follower> = read_layout(mpack, what)?;
+            Ok((BlobFormat::FollowerRecords, records.into_iter().map(|record| record.username).collect()))
+        }
+        Some(found) => {
+            Err(TrackerError::WrongFormat { expected: "username list or follower records", found: found.name() })
+        }
+        None => Err(TrackerError::BlobDecode {
+            what: what.to_string(),
+            bytes: mpack.len(),
+            detail: "not a recognized follower blob format".to_string(),
+        }),
+    }
+}
+
+/// Checks whether two blobs describe the same set of followers, whatever
+/// layout each was written with.
+///
+/// Both blobs are decoded with format detection and compared as sets, so a
+/// plain username list and a list of `Follower` records with the same
+/// usernames are equivalent, regardless of order or duplicates. Sync code can
+/// use this to skip no-op writes after a storage format change.
+///
+/// # Arguments
+///
+/// * `a_mpack`: A byte slice (`&[u8]`) containing the first follower blob.
+/// * `b_mpack`: A byte slice (`&[u8]`) containing the second follower blob.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(bool)`: Whether both blobs hold the same usernames.
+/// - `Err(JsValue)`: A JavaScript error object if either blob is not in a
+///   recognized format.
+#[wasm_bindgen]
+pub fn blobs_equivalent(a_mpack: &[u8], b_mpack: &[u8]) -> Result<bool, JsValue> {
+    let (_, a) = decode_any_followers(a_mpack, "first blob")?;
+    let (_, b) = decode_any_followers(b_mpack, "second blob")?;
+
+    let a: HashSet<String> = a.into_iter().collect();
+    let b: HashSet<String> = b.into_iter().collect();
+    Ok(a == b)
+}
+
+/// The result of `inspect_blob`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlobStatus {
+    /// `"empty"`, `"valid"` or `"corrupt"`.
+    pub state: &'static str,
+    /// The number of entries in a valid blob.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    /// Why a corrupt blob could not be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Reports whether a stored follower blob is missing, readable, or corrupt.
+///
+/// Unlike the decoding functions, this never throws on bad data: corruption is
+/// reported in the returned status, so the extension can tell a first run
+/// ("no data yet") apart from stored data it cannot read and message each
+/// clearly. Both the username-list and `Follower`-record layouts are valid.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing the stored blob, or an empty
+///   slice if nothing has been stored yet.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ state, count?, detail? }`. `state` is
+///   `"empty"` for an empty slice, `"valid"` (with `count`) for a readable blob,
+///   or `"corrupt"` (with `detail`) otherwise.
+/// - `Err(JsValue)`: A JavaScript error object if the status cannot be converted
+///   to a JavaScript value.
+#[wasm_bindgen]
+pub fn inspect_blob(mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let status = if mpack.is_empty() {
+        BlobStatus { state: "empty", count: None, detail: None }
+    } else {
+        match read_any_followers(mpack, "stored blob") {
+            Ok((_, usernames)) => BlobStatus { state: "valid", count: Some(usernames.len()), detail: None },
+            Err(error) => BlobStatus { state: "corrupt", count: None, detail: Some(error.to_string()) },
+        }
+    };
+
+    to_js(&status)
+}
+
+/// Sorts the usernames in a follower blob by a caller-chosen key, so large
+/// lists can be ordered for display without a round trip through JavaScript.
+///
+/// Sorting by `"username"` works with either blob layout. Sorting by
+/// `"full_name"` needs `Follower` records; accounts without a full name are
+/// listed last in both directions, and ties are broken by username in the same
+/// direction as the sort, so a descending sort reverses an ascending one. Blobs do
+/// not record when a follower was first seen, so `"first_seen"` is not a
+/// supported key.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing the follower blob, serialized in
+///   MessagePack format.
+/// * `key`: The sort key, `"username"` or `"full_name"`.
+/// * `descending`: Whether to sort from Z to A instead of A to Z.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of strings with the sorted usernames.
+/// - `Err(JsValue)`: A JavaScript error object if the key is unknown, if it needs
+///   data the blob does not contain, or if deserialization fails.
+#[wasm_bindgen]
+pub fn sort_followers(mpack: &[u8], key: &str, descending: bool) -> Result<JsValue, JsValue> {
+    let usernames: Vec<String> = match key {
+        "username" => {
+            let (_, usernames) = decode_any_followers(mpack, "followers")?;
+            let mut usernames = sorted(usernames);
+            if descending {
+                usernames.reverse();
+            }
+            usernames
+        }
+        "full_name" => {
+            let mut records = decode_follower_records(mpack, "follower records")?;
+            records.sort_by(|a, b| match (&a.full_name, &b.full_name) {
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                _ => {
+                    let ascending = a.full_name.cmp(&b.full_name).then_with(|| a.username.cmp(&b.username));
+                    if descending {
+                        ascending.reverse()
+                    } else {
+                        ascending
+                    }
+                }
+            });
+            records.into_iter().map(|record| record.username).collect()
+        }
+        other => {
+            return Err(JsValue::from_str(&format!(
+                "Unknown sort key '{}': expected \"username\" or \"full_name\"",
+                other
+            )))
+        }
+    };
+
+    to_js(&usernames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(usernames: &[&str]) -> Vec<u8> {
+        let records: Vec<Follower> = usernames
+            .iter()
+            .zip(1..)
+            .map(|(username, user_id)| Follower {
+                username: username.to_string(),
+                user_id,
+                full_name: None,
+                is_verified: None,
+            })
+            .collect();
+        rmp_serde::to_vec(&records).unwrap()
+    }
+
+    fn plain(usernames: &[&str]) -> Vec<u8> {
+        rmp_serde::to_vec(usernames).unwrap()
+    }
+
+    #[test]
+    fn record_blob_is_equivalent_to_its_plain_username_list() {
+        let records = records(&["alice", "bob", "carol"]);
+        assert!(blobs_equivalent(&records, &plain(&["carol", "alice", "bob", "alice"])).unwrap());
+        assert!(!blobs_equivalent(&records, &plain(&["alice", "bob"])).unwrap());
+        assert!(blobs_equivalent(&[], &plain(&[])).unwrap());
+    }
+
+    #[test]
+    fn map_encoded_blob_reads_as_the_same_usernames() {
+        let map: std::collections::BTreeMap<&str, u32> = [("alice", 1), ("bob", 2), ("carol", 3)].into();
+        let map_blob = rmp_serde::to_vec(&map).unwrap();
+
+        assert_eq!(detect_format(&map_blob), Some(BlobFormat::Usernames));
+        assert_eq!(crate::decode_followers(&map_blob, "followers").unwrap(), ["alice", "bob", "carol"]);
+        assert!(blobs_equivalent(&map_blob, &plain(&["bob", "carol", "alice"])).unwrap());
+    }
+
+    #[test]
+    fn detects_each_layout_from_the_leading_bytes() {
+        assert_eq!(detect_format(&plain(&["alice", "bob"])), Some(BlobFormat::Usernames));
+        assert_eq!(detect_format(&plain(&[])), Some(BlobFormat::Usernames));
+        assert_eq!(detect_format(&records(&["alice", "bob"])), Some(BlobFormat::FollowerRecords));
+
+        // zlib at the default and best compression levels, and gzip.
+        assert_eq!(detect_format(&[0x78, 0x9c, 0x01, 0x02]), Some(BlobFormat::Compressed));
+        assert_eq!(detect_format(&[0x78, 0xda, 0x01, 0x02]), Some(BlobFormat::Compressed));
+        assert_eq!(detect_format(&[0x1f, 0x8b, 0x08, 0x00]), Some(BlobFormat::Compressed));
+        // A zlib method byte with a bad header checksum is not compressed.
+        assert_eq!(detect_format(&[0x78, 0x9d, 0x01, 0x02]), None);
+
+        let mut signed = records(&["alice"]);
+        signed.extend_from_slice(&[0xc1; TAG_LEN]);
+        assert_eq!(detect_format(&signed), Some(BlobFormat::Signed));
+        let mut trailing = plain(&["alice"]);
+        trailing.extend_from_slice(&[0, 0, 0]);
+        assert_eq!(detect_format(&trailing), None);
+        assert_eq!(detect_format(&[0x92, 0x01, 0x02]), None);
+    }
+
+    #[test]
+    fn value_len_walks_nested_and_sized_markers() {
+        let blob = plain(&["a".repeat(300).as_str(), "b"]);
+        assert_eq!(value_len(&blob), Some(blob.len()));
+        let records = records(&["alice", "bob"]);
+        assert_eq!(value_len(&[records.as_slice(), &[0xff]].concat()), Some(records.len()));
+        // A uint 16 and a fixext 4 inside a one-element map.
+        assert_eq!(value_len(&[0x81, 0xcd, 0x01, 0x2c, 0xd6, 0x01, 0, 0, 0, 0]), Some(10));
+        assert_eq!(value_len(&[0x93, 0x01, 0x02]), None);
+    }
+}
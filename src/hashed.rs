@@ -0,0 +1,115 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: hashed.rs
+* Description: Privacy-preserving snapshots. Instead of plaintext usernames,
+* a hashed snapshot stores a salted HMAC-SHA256 digest of each username, so
+* it can be synced through a server or another device without revealing who
+* the followers are. Diffs are computed by hashing the fresh scrape with the
+* same salt; the salt must therefore stay the same across snapshots.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashSet;
+
+use crate::{decode_followers, encode_mpack, followers_from_js, sorted, to_js};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Serializes a follower list as salted username hashes instead of plaintext.
+///
+/// Each unique username is replaced by the lowercase hex HMAC-SHA256 of the
+/// username keyed with `salt`. The result is a sorted MessagePack array of
+/// strings, so it has the same layout as a plain username list.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+/// * `salt`: A secret salt. Use the same salt for every snapshot that will be
+///   compared, and keep it off the sync server.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: A byte vector (`Uint8Array` in JS) of the serialized hashes.
+/// - `Err(JsValue)`: A JavaScript error object if the salt is empty, the input is
+///   invalid, or serialization fails.
+#[wasm_bindgen]
+pub fn serialize_hashed_followers(followers_js: JsValue, salt: &str) -> Result<Vec<u8>, JsValue> {
+    let followers = followers_from_js(followers_js, "followers")?;
+    let hashes = hash_usernames(&followers, salt)?;
+    encode_mpack(&sorted(hashes.into_iter().collect()))
+}
+
+/// Finds unfollowers against a hashed snapshot.
+///
+/// The new usernames are hashed with `salt` exactly as in
+/// `serialize_hashed_followers` and compared with the stored hashes. Since the
+/// stored side has no plaintext, the departed accounts are reported by hash;
+/// the caller can map them back to usernames from any plaintext list it still
+/// holds locally.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_hashed_mpack`: A byte slice (`&[u8]`) containing the previous snapshot, as
+///   produced by `serialize_hashed_followers`.
+/// * `salt`: The salt the previous snapshot was hashed with.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the hashes of the
+///   departed accounts.
+/// - `Err(JsValue)`: A JavaScript error object if the salt is empty, or if
+///   deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_hashed(new_followers_js: JsValue, old_hashed_mpack: &[u8], salt: &str) -> Result<JsValue, JsValue> {
+    let old_hashes: HashSet<String> = decode_followers(old_hashed_mpack, "old hashed followers")?.into_iter().collect();
+    let new_followers = followers_from_js(new_followers_js, "new followers")?;
+    let new_hashes = hash_usernames(&new_followers, salt)?;
+
+    to_js(&sorted(old_hashes.difference(&new_hashes).cloned().collect()))
+}
+
+/// Hashes every username with HMAC-SHA256 keyed by `salt`, de-duplicating.
+fn hash_usernames(usernames: &[String], salt: &str) -> Result<HashSet<String>, JsValue> {
+    if salt.is_empty() {
+        return Err(JsValue::from_str("Salt must not be empty"));
+    }
+    let mac = HmacSha256::new_from_slice(salt.as_bytes())
+        .map_err(|e| JsValue::from_str(&format!("Invalid salt: {}", e)))?;
+
+    Ok(usernames
+        .iter()
+        .map(|username| {
+            let mut mac = mac.clone();
+            mac.update(username.as_bytes());
+            mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE_SALTED: &str = "dc663a1de92b83cd9b6522902cf9420e70757f1eb293f2be7d881953cb2dc149";
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn hashing_is_consistent_across_calls_and_depends_on_the_salt() {
+        let first = hash_usernames(&names(&["alice", "bob", "alice"]), "salt").unwrap();
+        let second = hash_usernames(&names(&["bob", "alice"]), "salt").unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 2);
+        assert!(first.contains(ALICE_SALTED));
+
+        let other_salt = hash_usernames(&names(&["alice"]), "pepper").unwrap();
+        assert!(!other_salt.contains(ALICE_SALTED));
+    }
+}
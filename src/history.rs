@@ -0,0 +1,692 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: history.rs
+* Description: The check history log and the analytics built on top of it.
+* Every time the extension compares a fresh scrape against the stored list it
+* can append a `HistoryEntry` recording when the check ran, how many followers
+* there were, and who was gained or lost. The log is stored as a MessagePack
+* array of entries and feeds growth metrics and other trend analyses.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::error::{validate_duration, validate_timestamp, TrackerError};
+use crate::interner::{Interner, Username};
+use crate::{decode_followers, decode_records, encode_mpack, followers_from_js, records_from_js, sorted, to_js};
+
+/// The outcome of a single follower check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the check ran, in milliseconds since the Unix epoch.
+    pub timestamp: f64,
+    /// The number of unique followers found by the check.
+    pub follower_count: usize,
+    /// Accounts that started following since the previous check, sorted.
+    pub new_followers: Vec<String>,
+    /// Accounts that stopped following since the previous check, sorted.
+    pub unfollowers: Vec<String>,
+}
+
+/// Records a follower check by appending a `HistoryEntry` to the history log.
+///
+/// The entry is derived by diffing the new follower list against the previous
+/// one, so this can be called right alongside `find_unfollowers` with the same
+/// inputs.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the existing history log.
+///   An empty slice starts a new log.
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `timestamp`: When the check ran, in milliseconds since the Unix epoch.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The updated history log, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if `timestamp` is not a valid
+///   timestamp, or if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn append_history_entry(
+    history_mpack: &[u8],
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    timestamp: f64,
+) -> Result<Vec<u8>, JsValue> {
+    validate_timestamp("timestamp", timestamp)?;
+    let mut history = decode_history(history_mpack)?;
+    let old_set: HashSet<String> = decode_followers(old_followers_mpack, "old followers")?.into_iter().collect();
+    let new_set: HashSet<String> = followers_from_js(new_followers_js, "new followers")?.into_iter().collect();
+
+    history.push(HistoryEntry {
+        timestamp,
+        follower_count: new_set.len(),
+        new_followers: sorted(new_set.difference(&old_set).cloned().collect()),
+        unfollowers: sorted(old_set.difference(&new_set).cloned().collect()),
+    });
+
+    encode_mpack(&history)
+}
+
+/// Computes the relative follower growth over a period of time.
+///
+/// The most recent entry in the history is compared against the entry whose
+/// timestamp is closest to `now - window_ms`, and the growth is returned as
+/// `(latest_count - old_count) / old_count`. For example, `0.05` means the
+/// follower count grew by 5% over the window.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+/// * `window_ms`: The length of the period to measure, in milliseconds.
+/// * `now`: The current time in milliseconds since the Unix epoch.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(f64)`: The growth rate. This is `NaN` when the baseline entry recorded
+///   zero followers, since no meaningful rate exists.
+/// - `Err(JsValue)`: A JavaScript error object if `window_ms` is not a valid duration
+///   or `now` is not a valid timestamp, or if the history is empty or cannot be
+///   deserialized.
+#[wasm_bindgen]
+pub fn growth_rate(history_mpack: &[u8], window_ms: f64, now: f64) -> Result<f64, JsValue> {
+    validate_duration("window_ms", window_ms)?;
+    validate_timestamp("now", now)?;
+    let history = decode_history(history_mpack)?;
+    let target = now - window_ms;
+
+    let latest = history
+        .iter()
+        .max_by(|a, b| a.timestamp.total_cmp(&b.timestamp))
+        .ok_or_else(|| JsValue::from_str("Cannot compute growth rate: history is empty"))?;
+    let baseline = history
+        .iter()
+        .min_by(|a, b| (a.timestamp - target).abs().total_cmp(&(b.timestamp - target).abs()))
+        .ok_or_else(|| JsValue::from_str("Cannot compute growth rate: history is empty"))?;
+
+    if baseline.follower_count == 0 {
+        return Ok(f64::NAN);
+    }
+    Ok((latest.follower_count as f64 - baseline.follower_count as f64) / baseline.follower_count as f64)
+}
+
+/// Decides whether the latest number of unfollowers is unusually high compared
+/// to the account's own history.
+///
+/// The mean and (population) standard deviation of the per-check unfollower
+/// counts are computed from the history log, and the drop is flagged when
+/// `latest_lost > mean + std_devs * stddev`. This scales with the account:
+/// 200 unfollowers in one check is routine for a huge account but alarming for
+/// a small one.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+/// * `latest_lost`: The number of unfollowers found by the latest check.
+/// * `std_devs`: How many standard deviations above the mean count as anomalous.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(bool)`: Whether the drop is anomalous. Always `false` when the history
+///   has fewer than two entries, since no meaningful baseline exists.
+/// - `Err(JsValue)`: A JavaScript error object if the history cannot be deserialized.
+#[wasm_bindgen]
+pub fn is_anomalous_drop(history_mpack: &[u8], latest_lost: usize, std_devs: f64) -> Result<bool, JsValue> {
+    let history = decode_history(history_mpack)?;
+    if history.len() < 2 {
+        return Ok(false);
+    }
+
+    let counts: Vec<f64> = history.iter().map(|entry| entry.unfollowers.len() as f64).collect();
+    let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+    let variance = counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / counts.len() as f64;
+
+    Ok(latest_lost as f64 > mean + std_devs * variance.sqrt())
+}
+
+/// Finds the check that recorded the most unfollowers, for a "worst day" stat.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: The `HistoryEntry` with the most unfollowers, or `null` if the
+///   history is empty. Ties resolve to the earliest entry.
+/// - `Err(JsValue)`: A JavaScript error object if the history cannot be deserialized.
+#[wasm_bindgen]
+pub fn peak_unfollower_event(history_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let history = decode_history(history_mpack)?;
+
+    let peak = history.iter().reduce(|best, entry| {
+        let more = entry.unfollowers.len() > best.unfollowers.len();
+        let tie_but_earlier =
+            entry.unfollowers.len() == best.unfollowers.len() && entry.timestamp < best.timestamp;
+        if more || tie_but_earlier {
+            entry
+        } else {
+            best
+        }
+    });
+
+    match peak {
+        Some(entry) => to_js(entry),
+        None => Ok(JsValue::NULL),
+    }
+}
+
+/// The first time the follower count reached a milestone.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Milestone {
+    pub milestone: usize,
+    pub timestamp: f64,
+}
+
+/// Finds when each follower-count milestone was first reached, for
+/// celebratory UI such as "you hit 1000 followers on this date".
+///
+/// A milestone counts as reached by the earliest check whose recorded
+/// follower count is at least the threshold.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+/// * `milestones_js`: A `JsValue` from JavaScript, expected to be an array of
+///   follower-count thresholds, e.g. `[100, 500, 1000]`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of `{ milestone, timestamp }` objects sorted by
+///   milestone. Thresholds that were never reached are omitted.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn follower_milestones(history_mpack: &[u8], milestones_js: JsValue) -> Result<JsValue, JsValue> {
+    let mut history = decode_history(history_mpack)?;
+    let mut thresholds: Vec<usize> = records_from_js(milestones_js, "milestones")?;
+    history.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+    thresholds.sort_unstable();
+    thresholds.dedup();
+
+    let reached: Vec<Milestone> = thresholds
+        .into_iter()
+        .filter_map(|milestone| {
+            history
+                .iter()
+                .find(|entry| entry.follower_count >= milestone)
+                .map(|entry| Milestone { milestone, timestamp: entry.timestamp })
+        })
+        .collect();
+
+    to_js(&reached)
+}
+
+/// The followers gained by a single check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecentGains {
+    pub timestamp: f64,
+    pub usernames: Vec<String>,
+}
+
+/// Lists the followers gained in each of the last `n` checks, for a "recent
+/// activity" panel.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+/// * `n`: How many of the most recent checks to include.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of `{ timestamp, usernames }` objects, newest
+///   first. Fewer than `n` objects are returned when the history is shorter.
+/// - `Err(JsValue)`: A JavaScript error object if the history cannot be deserialized.
+#[wasm_bindgen]
+pub fn recent_gains(history_mpack: &[u8], n: usize) -> Result<JsValue, JsValue> {
+    let mut history = decode_history(history_mpack)?;
+    history.sort_by(|a, b| b.timestamp.total_cmp(&a.timestamp));
+
+    let gains: Vec<RecentGains> = history
+        .into_iter()
+        .take(n)
+        .map(|entry| RecentGains { timestamp: entry.timestamp, usernames: entry.new_followers })
+        .collect();
+
+    to_js(&gains)
+}
+
+/// Lists every account that has ever unfollowed, for a "hall of departures".
+///
+/// The `unfollowers` of every check are combined and de-duplicated, so an
+/// account that refollowed and later left again is listed once.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the unfollowers.
+/// - `Err(JsValue)`: A JavaScript error object if the history cannot be deserialized.
+#[wasm_bindgen]
+pub fn all_time_unfollowers(history_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let history = decode_interned_history(history_mpack)?;
+    let departed: HashSet<Username> = history.into_iter().flat_map(|entry| entry.unfollowers).collect();
+    to_js(&sorted_owned(departed))
+}
+
+/// Estimates how long it takes for half of a cohort of new followers to leave.
+///
+/// Every check after the first starts a cohort: the followers it gained. The
+/// cohort is followed through the later checks, and a follower counts as gone
+/// from the first check that lists them as an unfollower, even if they follow
+/// again later. The time at which the cohort fell to half its size is
+/// interpolated linearly between the two checks around that point. The result
+/// is the median over all cohorts that have reached half; cohorts still above
+/// half are left out. The first check is skipped because the followers it
+/// "gained" are simply everyone who already followed.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(f64)`: The median retention half-life in milliseconds.
+/// - `Err(JsValue)`: A JavaScript error object if no cohort has lost half of its
+///   members yet, or if the history cannot be deserialized.
+#[wasm_bindgen]
+pub fn retention_half_life(history_mpack: &[u8]) -> Result<f64, JsValue> {
+    let mut history = decode_history(history_mpack)?;
+    history.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+
+    let mut half_lives: Vec<f64> = Vec::new();
+    for (start, cohort_entry) in history.iter().enumerate().skip(1) {
+        let mut remaining: HashSet<&String> = cohort_entry.new_followers.iter().collect();
+        if remaining.is_empty() {
+            continue;
+        }
+        let half = remaining.len() as f64 / 2.0;
+
+        let (mut previous_time, mut previous_size) = (cohort_entry.timestamp, remaining.len() as f64);
+        for entry in &history[start + 1..] {
+            for username in &entry.unfollowers {
+                remaining.remove(username);
+            }
+            let size = remaining.len() as f64;
+            if size <= half {
+                let fraction = (previous_size - half) / (previous_size - size);
+                half_lives.push(previous_time - cohort_entry.timestamp + fraction * (entry.timestamp - previous_time));
+                break;
+            }
+            (previous_time, previous_size) = (entry.timestamp, size);
+        }
+    }
+
+    if half_lives.is_empty() {
+        return Err(JsValue::from_str(
+            "Cannot compute retention half-life: no cohort of new followers has lost half of its members yet",
+        ));
+    }
+    half_lives.sort_by(f64::total_cmp);
+    let mid = half_lives.len() / 2;
+    Ok(if half_lives.len().is_multiple_of(2) { (half_lives[mid - 1] + half_lives[mid]) / 2.0 } else { half_lives[mid] })
+}
+
+/// Combines two history logs, e.g. a restored backup and the current log.
+///
+/// Entries from both logs are merged and sorted by timestamp. Entries that
+/// share a timestamp are recorded by the same check, so only one is kept: the
+/// one from `a_mpack`.
+///
+/// # Arguments
+///
+/// * `a_mpack`: A byte slice (`&[u8]`) containing the preferred history log.
+/// * `b_mpack`: A byte slice (`&[u8]`) containing the other history log.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The merged history log, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if either log cannot be deserialized.
+#[wasm_bindgen]
+pub fn merge_histories(a_mpack: &[u8], b_mpack: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut merged = decode_history(a_mpack)?;
+    merged.extend(decode_history(b_mpack)?);
+
+    // The sort is stable, so among equal timestamps entries from `a` come first
+    // and survive the dedup.
+    merged.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+    merged.dedup_by(|later, earlier| later.timestamp == earlier.timestamp);
+
+    encode_mpack(&merged)
+}
+
+/// Lists the accounts that unfollowed during a period of time, for queries such
+/// as "show me who left last month".
+///
+/// The `unfollowers` of every check whose timestamp falls within `[start, end]`,
+/// both ends included, are combined and de-duplicated.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+/// * `start`: The start of the period, in milliseconds since the Unix epoch.
+/// * `end`: The end of the period, in milliseconds since the Unix epoch.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the unfollowers. Empty
+///   if `start` is after `end`.
+/// - `Err(JsValue)`: A JavaScript error object if either bound is not a valid
+///   timestamp, or if the history cannot be deserialized.
+#[wasm_bindgen]
+pub fn unfollowers_in_range(history_mpack: &[u8], start: f64, end: f64) -> Result<JsValue, JsValue> {
+    validate_timestamp("start", start)?;
+    validate_timestamp("end", end)?;
+    let history = decode_interned_history(history_mpack)?;
+
+    let departed: HashSet<Username> = history
+        .into_iter()
+        .filter(|entry| entry.timestamp >= start && entry.timestamp <= end)
+        .flat_map(|entry| entry.unfollowers)
+        .collect();
+    to_js(&sorted_owned(departed))
+}
+
+/// Reconstructs the follower set as it was at a point in time, for "who was
+/// following me on this date?" queries.
+///
+/// The gains and losses of every check at or before `timestamp` are replayed
+/// in order starting from an empty list, so the result is exact as long as the
+/// log was started on the very first check (when every follower is recorded as
+/// new).
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+/// * `timestamp`: The point in time, in milliseconds since the Unix epoch.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the followers at the
+///   latest check at or before `timestamp`. Empty if `timestamp` precedes the log.
+/// - `Err(JsValue)`: A JavaScript error object if `timestamp` is not a valid
+///   timestamp, or if the history cannot be deserialized.
+#[wasm_bindgen]
+pub fn reconstruct_at(history_mpack: &[u8], timestamp: f64) -> Result<JsValue, JsValue> {
+    validate_timestamp("timestamp", timestamp)?;
+    let history = decode_interned_history(history_mpack)?;
+    let followers = replay_until(&history, timestamp);
+    to_js(&sorted_owned(followers))
+}
+
+/// The difference between the follower sets at two checks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnapshotComparison {
+    /// The timestamp of the check used for `ts_a`.
+    pub timestamp_a: f64,
+    /// The timestamp of the check used for `ts_b`.
+    pub timestamp_b: f64,
+    /// Followers at `timestamp_b` who were not following at `timestamp_a`, sorted.
+    pub gained: Vec<String>,
+    /// Followers at `timestamp_a` who were no longer following at `timestamp_b`, sorted.
+    pub lost: Vec<String>,
+    /// The change in follower count from `timestamp_a` to `timestamp_b`.
+    pub net_change: i64,
+}
+
+/// Compares the follower sets at two points in history, for reports such as
+/// "changes between March 1 and June 1".
+///
+/// Each timestamp is matched to the check closest to it, and the follower set
+/// at that check is reconstructed as in `reconstruct_at`. The checks need not
+/// be adjacent; everything that happened in between is folded into one diff.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+/// * `ts_a`: The earlier point in time, in milliseconds since the Unix epoch.
+/// * `ts_b`: The later point in time, in milliseconds since the Unix epoch.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object
+///   `{ timestamp_a, timestamp_b, gained, lost, net_change }`.
+/// - `Err(JsValue)`: A JavaScript error object if either timestamp is invalid, if
+///   the history is empty, or if it cannot be deserialized.
+#[wasm_bindgen]
+pub fn compare_snapshots_at(history_mpack: &[u8], ts_a: f64, ts_b: f64) -> Result<JsValue, JsValue> {
+    validate_timestamp("ts_a", ts_a)?;
+    validate_timestamp("ts_b", ts_b)?;
+    let history = decode_interned_history(history_mpack)?;
+
+    let nearest = |target: f64| {
+        history
+            .iter()
+            .map(|entry| entry.timestamp)
+            .min_by(|a, b| (a - target).abs().total_cmp(&(b - target).abs()))
+            .ok_or_else(|| JsValue::from_str("Cannot compare snapshots: history is empty"))
+    };
+    let (timestamp_a, timestamp_b) = (nearest(ts_a)?, nearest(ts_b)?);
+
+    let set_a = replay_until(&history, timestamp_a);
+    let set_b = replay_until(&history, timestamp_b);
+    to_js(&SnapshotComparison {
+        timestamp_a,
+        timestamp_b,
+        gained: sorted_owned(set_b.difference(&set_a).cloned()),
+        lost: sorted_owned(set_a.difference(&set_b).cloned()),
+        net_change: set_b.len() as i64 - set_a.len() as i64,
+    })
+}
+
+/// Replays the history log in timestamp order up to and including `timestamp`,
+/// returning the follower set at that point.
+pub(crate) fn replay_until(history: &[InternedEntry], timestamp: f64) -> HashSet<Username> {
+    let mut entries: Vec<&InternedEntry> = history.iter().filter(|entry| entry.timestamp <= timestamp).collect();
+    entries.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+
+    let mut followers = HashSet::new();
+    for entry in entries {
+        for username in &entry.unfollowers {
+            followers.remove(username);
+        }
+        followers.extend(entry.new_followers.iter().cloned());
+    }
+    followers
+}
+
+/// Copies interned usernames into a sorted list, ready to be returned to
+/// JavaScript.
+fn sorted_owned(usernames: impl IntoIterator<Item = Username>) -> Vec<String> {
+    sorted(usernames.into_iter().map(|username| username.to_string()).collect())
+}
+
+/// Deserializes a history log, treating an empty slice as an empty log.
+pub(crate) fn decode_history(history_mpack: &[u8]) -> Result<Vec<HistoryEntry>, JsValue> {
+    decode_records(history_mpack, "history")
+}
+
+/// A `HistoryEntry` with interned usernames, for the analytics that keep the
+/// whole log in memory at once.
+pub(crate) struct InternedEntry {
+    pub(crate) timestamp: f64,
+    pub(crate) new_followers: Vec<Username>,
+    pub(crate) unfollowers: Vec<Username>,
+}
+
+/// The layout of a `HistoryEntry` with the usernames borrowed from the blob.
+#[derive(Deserialize)]
+struct BorrowedEntry<'a> {
+    timestamp: f64,
+    #[serde(rename = "follower_count")]
+    _follower_count: usize,
+    #[serde(borrow)]
+    new_followers: Vec<&'a str>,
+    #[serde(borrow)]
+    unfollowers: Vec<&'a str>,
+}
+
+/// Deserializes a history log like `decode_history`, but interns the
+/// usernames: they are read straight out of the blob and each unique one is
+/// allocated once, however many entries mention it.
+pub(crate) fn decode_interned_history(history_mpack: &[u8]) -> Result<Vec<InternedEntry>, JsValue> {
+    if history_mpack.is_empty() {
+        return Ok(Vec::new());
+    }
+    let entries: Vec<BorrowedEntry> = rmp_serde::from_slice(history_mpack).map_err(|e| TrackerError::BlobDecode {
+        what: "history".to_string(),
+        bytes: history_mpack.len(),
+        detail: e.to_string(),
+    })?;
+
+    let mut interner = Interner::new();
+    let mut intern_all = |usernames: Vec<&str>| usernames.into_iter().map(|name| interner.intern(name)).collect();
+    Ok(entries
+        .into_iter()
+        .map(|entry| InternedEntry {
+            timestamp: entry.timestamp,
+            new_followers: intern_all(entry.new_followers),
+            unfollowers: intern_all(entry.unfollowers),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: f64, new_followers: &[&str], unfollowers: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            follower_count: 0,
+            new_followers: new_followers.iter().map(|s| s.to_string()).collect(),
+            unfollowers: unfollowers.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn decoding_allocates_each_username_once_across_snapshots() {
+        let history = decode_interned_history(&log(&[
+            entry(1.0, &["alice", "bob", "carol"], &[]),
+            entry(2.0, &["dave"], &["bob"]),
+            entry(3.0, &["bob"], &["carol"]),
+            entry(4.0, &[], &["alice", "bob"]),
+        ]))
+        .unwrap();
+
+        let mut allocations: Vec<*const u8> = history
+            .iter()
+            .flat_map(|entry| entry.new_followers.iter().chain(&entry.unfollowers))
+            .map(|username| username.as_ptr())
+            .collect();
+        assert_eq!(allocations.len(), 9);
+        allocations.sort_unstable();
+        allocations.dedup();
+        // Nine mentions of four accounts: only four strings were allocated.
+        assert_eq!(allocations.len(), 4);
+
+        assert_eq!(sorted_owned(replay_until(&history, 3.0)), ["alice", "bob", "dave"]);
+    }
+
+    fn counted(timestamp: f64, follower_count: usize) -> HistoryEntry {
+        HistoryEntry { follower_count, ..entry(timestamp, &[], &[]) }
+    }
+
+    fn log(history: &[HistoryEntry]) -> Vec<u8> {
+        rmp_serde::to_vec(history).unwrap()
+    }
+
+    const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+    #[test]
+    fn growth_rate_between_two_snapshots_a_week_apart() {
+        let history = log(&[counted(0.0, 200), counted(7.0 * DAY_MS, 250)]);
+        assert_eq!(growth_rate(&history, 7.0 * DAY_MS, 7.0 * DAY_MS).unwrap(), 0.25);
+    }
+
+    #[test]
+    fn growth_rate_uses_the_entry_closest_to_the_window_start() {
+        let history = log(&[counted(0.0, 100), counted(6.0 * DAY_MS, 200), counted(10.0 * DAY_MS, 300)]);
+        // now - window = 5 days, which is closest to the day-6 entry.
+        assert_eq!(growth_rate(&history, 5.0 * DAY_MS, 10.0 * DAY_MS).unwrap(), 0.5);
+        assert!(growth_rate(&log(&[counted(0.0, 0), counted(DAY_MS, 10)]), DAY_MS, DAY_MS).unwrap().is_nan());
+    }
+
+    fn with_losses(timestamp: f64, count: usize) -> HistoryEntry {
+        let unfollowers: Vec<String> = (0..count).map(|i| format!("lost{}", i)).collect();
+        HistoryEntry { unfollowers, ..entry(timestamp, &[], &[]) }
+    }
+
+    #[test]
+    fn flags_a_clear_outlier_against_the_historical_average() {
+        let history = log(&[with_losses(1.0, 2), with_losses(2.0, 3), with_losses(3.0, 2), with_losses(4.0, 3)]);
+        // Mean 2.5, standard deviation 0.5: the threshold at 2 deviations is 3.5.
+        assert!(is_anomalous_drop(&history, 50, 2.0).unwrap());
+        assert!(is_anomalous_drop(&history, 4, 2.0).unwrap());
+        assert!(!is_anomalous_drop(&history, 3, 2.0).unwrap());
+        assert!(!is_anomalous_drop(&log(&[with_losses(1.0, 0)]), 1000, 2.0).unwrap());
+    }
+
+    fn names(prefix: &str, range: std::ops::Range<usize>) -> Vec<String> {
+        range.map(|i| format!("{}{}", prefix, i)).collect()
+    }
+
+    #[test]
+    fn half_life_of_a_cohort_losing_a_quarter_per_day() {
+        let lost = |day: f64, range| HistoryEntry {
+            unfollowers: names("c", range),
+            ..entry(day * DAY_MS, &[], &[])
+        };
+        let history = log(&[
+            entry(0.0, &["already_following"], &[]),
+            HistoryEntry { new_followers: names("c", 0..8), ..entry(DAY_MS, &[], &[]) },
+            lost(2.0, 0..2),
+            lost(3.0, 2..4),
+            lost(4.0, 4..6),
+        ]);
+        assert_eq!(retention_half_life(&history).unwrap(), 2.0 * DAY_MS);
+    }
+
+    #[test]
+    fn half_life_interpolates_and_takes_the_median_over_cohorts() {
+        let mut history = vec![
+            entry(0.0, &["already_following"], &[]),
+            entry(DAY_MS, &["a0", "a1", "a2", "a3"], &[]),
+            // Cohort b is still above half when the log ends.
+            entry(2.0 * DAY_MS, &["b0", "b1", "b2", "b3"], &["a0"]),
+            // a drops from 3 to 1 between day 2 and day 5: half (2) at day 3.5.
+            entry(5.0 * DAY_MS, &[], &["a1", "a2", "b0"]),
+        ];
+        assert_eq!(retention_half_life(&log(&history)).unwrap(), 2.5 * DAY_MS);
+
+        history.push(entry(6.0 * DAY_MS, &["c0", "c1"], &[]));
+        history.push(entry(7.0 * DAY_MS, &[], &["c0"]));
+        // Half-lives of 2.5 days (a) and 1 day (c): the median is their mean.
+        assert_eq!(retention_half_life(&log(&history)).unwrap(), 1.75 * DAY_MS);
+    }
+
+    #[test]
+    fn merge_keeps_one_entry_per_timestamp_preferring_the_first_log() {
+        let a = log(&[counted(3.0, 30), counted(1.0, 10)]);
+        let b = log(&[counted(2.0, 20), counted(3.0, 99), counted(4.0, 40)]);
+
+        let merged: Vec<HistoryEntry> = rmp_serde::from_slice(&merge_histories(&a, &b).unwrap()).unwrap();
+        assert_eq!(merged, [counted(1.0, 10), counted(2.0, 20), counted(3.0, 30), counted(4.0, 40)]);
+    }
+}
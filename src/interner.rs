@@ -0,0 +1,54 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: interner.rs
+* Description: A small string interner for the history analytics. A history
+* log repeats the same usernames across many entries, and decoding it into
+* `HistoryEntry` values allocates a fresh `String` for every occurrence. The
+* interner keeps one shared `Rc<str>` per unique username instead, so a log
+* with many snapshots of the same audience holds each name once. Internal
+* only; nothing here is exposed to JavaScript.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// A shared handle to an interned username. Cloning it only bumps a count.
+pub(crate) type Username = Rc<str>;
+
+/// Hands out one shared `Username` per unique string.
+#[derive(Debug, Default)]
+pub(crate) struct Interner {
+    pool: HashSet<Username>,
+}
+
+impl Interner {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the handle for `name`, allocating it on first sight only.
+    pub(crate) fn intern(&mut self, name: &str) -> Username {
+        if let Some(shared) = self.pool.get(name) {
+            return Rc::clone(shared);
+        }
+        let shared: Username = Rc::from(name);
+        self.pool.insert(Rc::clone(&shared));
+        shared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_names_share_one_allocation() {
+        let mut interner = Interner::new();
+        let first = interner.intern("alice");
+        let second = interner.intern(&String::from("alice"));
+        let other = interner.intern("bob");
+
+        assert!(Rc::ptr_eq(&first, &second));
+        assert!(!Rc::ptr_eq(&first, &other));
+        assert_eq!(interner.pool.len(), 2);
+    }
+}
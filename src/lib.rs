@@ -10,13 +10,49 @@
 * SPDX-License-Identifier: Apache-2.0 */
 
 use wasm_bindgen::prelude::*;
-use std::collections::HashSet;
+use js_sys::{Array, Function, Map, Set};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use indexmap::IndexSet;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::error::{validate_usernames, TrackerError};
+use crate::format::{decode_layout, BlobFormat, UsernameList};
+
+pub mod audit;
+pub mod chunking;
+pub mod classify;
+pub mod delta;
+pub mod display;
+pub mod error;
+pub mod export;
+pub mod fingerprint;
+pub mod follower;
+pub mod format;
+pub mod hashed;
+pub mod history;
+mod interner;
+pub mod notes;
+pub mod query;
+pub mod rename;
+pub mod report;
+pub mod sampling;
+pub mod self_test;
+pub mod serializer;
+pub mod set_ops;
+pub mod signing;
+pub mod snapshots;
+pub mod state;
+pub mod storage;
+pub mod tombstone;
+pub mod tracker;
 
 // This function is called when the WASM module is instantiated.
 // It sets up a hook to forward Rust's panic messages to the browser's console.
-// This is invaluable for debugging.
+// This is invaluable for debugging. It is not called `main` because the WASM
+// test harness exports a function of that name.
 #[wasm_bindgen(start)]
-pub fn main() {
+pub fn start() {
     #[cfg(feature = "console_error_panic_hook")]
     console_error_panic_hook::set_once();
 }
@@ -31,46 +67,118 @@ pub fn main() {
 /// # Arguments
 ///
 /// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
-///   representing the latest list of followers.
+///   representing the latest list of followers, or a `PartialSnapshot` object.
 /// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
 ///   list, serialized in MessagePack format.
+/// * `own_username`: Optionally, the logged-in user's own handle. Scrapes sometimes
+///   pick it up by accident; it is removed from both lists (case-insensitively)
+///   before comparing.
 ///
 /// # Returns
 ///
 /// A `Result` containing either:
 /// - `Ok(JsValue)`: A JavaScript array of strings with the usernames of the unfollowers.
+///   Always empty if the new snapshot is marked incomplete: absences in an incomplete
+///   scrape cannot be trusted, so only `find_new_followers` has anything to report.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    own_username: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let unfollowers = find_removed(Perspective::Followers, new_followers_js, old_followers_mpack, own_username)?;
+
+    // Serialize the resulting vector of unfollowers back into a JsValue (JS array)
+    // and return it.
+    to_js(&unfollowers)
+}
+
+/// Finds accounts that were in the old following list but not in the new one,
+/// i.e. the accounts the user has unfollowed.
+///
+/// This is `find_unfollowers` seen from the other side of the relationship:
+/// it compares two snapshots of the accounts the user follows, using exactly
+/// the same set-difference engine and the same input handling.
+///
+/// # Arguments
+///
+/// * `new_following_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest following list, or a `PartialSnapshot` object.
+/// * `old_following_mpack`: A byte slice (`&[u8]`) containing the previous following
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of strings with the usernames no longer followed.
+///   Always empty if the new snapshot is marked incomplete.
 /// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
 #[wasm_bindgen]
-pub fn find_unfollowers(new_followers_js: JsValue, old_followers_mpack: &[u8]) -> Result<JsValue, JsValue> {
-    // Deserialize the old followers list from MessagePack bytes into a Rust Vec<String>.
+pub fn find_following_removed(new_following_js: JsValue, old_following_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    to_js(&find_removed(Perspective::Following, new_following_js, old_following_mpack, None)?)
+}
+
+/// Which of the user's account lists a comparison runs over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Perspective {
+    /// Accounts that follow the user. Removals are unfollowers.
+    Followers,
+    /// Accounts the user follows. Removals are accounts the user unfollowed.
+    Following,
+}
+
+impl Perspective {
+    /// The noun used for this list in error messages.
+    fn noun(self) -> &'static str {
+        match self {
+            Perspective::Followers => "followers",
+            Perspective::Following => "following",
+        }
+    }
+}
+
+/// The set-difference core shared by `find_unfollowers` and
+/// `find_following_removed`: returns the accounts in the old list that are
+/// missing from the new one.
+fn find_removed(
+    perspective: Perspective,
+    new_js: JsValue,
+    old_mpack: &[u8],
+    own_username: Option<String>,
+) -> Result<Vec<String>, JsValue> {
+    let noun = perspective.noun();
+
+    // Deserialize the old list from MessagePack bytes into a Rust Vec<String>.
     // If the input is empty (first run), initialize an empty vector.
-    let old_followers: Vec<String> = if old_followers_mpack.is_empty() {
-        Vec::new()
-    } else {
-        rmp_serde::from_slice(old_followers_mpack)
-            .map_err(|e| JsValue::from_str(&format!("Failed to deserialize old followers: {}", e)))?
-    };
+    let mut old_accounts = decode_followers(old_mpack, &format!("old {}", noun))?;
+
+    // Deserialize the new list from the JavaScript JsValue. Both a plain array
+    // and a `PartialSnapshot` object are accepted.
+    let new_snapshot = new_snapshot_from_js(new_js, &format!("new {}", noun))?;
+
+    // An incomplete scrape may simply have stopped before reaching some accounts,
+    // so reporting them as removed would produce false positives.
+    if !new_snapshot.complete {
+        state::log(&format!("new {} list is incomplete, skipping removal detection", noun));
+        return Ok(Vec::new());
+    }
+    let mut new_accounts = new_snapshot.followers;
 
-    // Deserialize the new followers list from the JavaScript JsValue into a Rust Vec<String>.
-    let new_followers: Vec<String> = serde_wasm_bindgen::from_value(new_followers_js)
-        .map_err(|e| JsValue::from_str(&format!("Failed to deserialize new followers: {}", e)))?;
+    // Strip the user's own handle so a self-entry can't show up as removed.
+    if let Some(own) = own_username.as_deref() {
+        remove_own_username(&mut old_accounts, own);
+        remove_own_username(&mut new_accounts, own);
+    }
 
     // Convert the vectors into HashSets for efficient comparison.
     // This is the core performance optimization.
-    let old_followers_set: HashSet<String> = old_followers.into_iter().collect();
-    let new_followers_set: HashSet<String> = new_followers.into_iter().collect();
-
-    // Calculate the difference. The result is an iterator of usernames that are
-    // in the old set but not in the new set.
-    let unfollowers: Vec<String> = old_followers_set
-        .difference(&new_followers_set)
-        .cloned()
-        .collect();
+    let old_set: HashSet<String> = old_accounts.into_iter().collect();
+    let new_set: HashSet<String> = new_accounts.into_iter().collect();
 
-    // Serialize the resulting vector of unfollowers back into a JsValue (JS array)
-    // and return it.
-    serde_wasm_bindgen::to_value(&unfollowers)
-        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+    // Calculate the difference: the accounts that are in the old set but not
+    // in the new set.
+    Ok(old_set.difference(&new_set).cloned().collect())
 }
 
 /// Serializes a list of follower usernames into the MessagePack binary format.
@@ -83,20 +191,977 @@ pub fn find_unfollowers(new_followers_js: JsValue, old_followers_mpack: &[u8]) -
 /// # Arguments
 ///
 /// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+///   A `Set` of strings, or a `Map` keyed by username, is accepted as well.
+/// * `own_username`: Optionally, the logged-in user's own handle, which is removed
+///   from the list (case-insensitively) before serializing.
 ///
 /// # Returns
 ///
 /// A `Result` containing either:
 /// - `Ok(Vec<u8>)`: A byte vector (`Uint8Array` in JS) of the serialized data.
-/// - `Err(JsValue)`: A JavaScript error object if the input is invalid or
-///   serialization fails.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid, a username
+///   is longer than `MAX_USERNAME_CHARS`, or serialization fails.
 #[wasm_bindgen]
-pub fn serialize_followers_to_mpack(followers_js: JsValue) -> Result<Vec<u8>, JsValue> {
-    // Deserialize the JSValue into a Rust vector of strings.
-    let followers: Vec<String> = serde_wasm_bindgen::from_value(followers_js)
-        .map_err(|e| JsValue::from_str(&format!("Failed to deserialize followers for serialization: {}", e)))?;
+pub fn serialize_followers_to_mpack(followers_js: JsValue, own_username: Option<String>) -> Result<Vec<u8>, JsValue> {
+    // Deserialize the JSValue (an Array, Set or Map of usernames) into a Rust
+    // vector of strings. Malformed entries, such as a whole DOM subtree captured
+    // as a username, are rejected here before they bloat storage.
+    let mut followers = followers_from_js(followers_js, "followers for serialization")?;
+
+    // A self-entry would skew follower counts in every later comparison.
+    if let Some(own) = own_username.as_deref() {
+        remove_own_username(&mut followers, own);
+    }
 
     // Serialize the vector into MessagePack format.
     rmp_serde::to_vec(&followers)
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize to MessagePack: {}", e)))
 }
+
+/// Serializes a list of follower usernames into MessagePack, preserving order.
+///
+/// Instagram lists followers newest-first, so the scraped order doubles as a
+/// "most recent followers" ranking. Converting through a `HashSet` would lose
+/// it; this function de-duplicates with an insertion-ordered set instead, so
+/// each username keeps the position of its first occurrence.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: A byte vector (`Uint8Array` in JS) of the serialized data.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid or
+///   serialization fails.
+#[wasm_bindgen]
+pub fn serialize_followers_ordered(followers_js: JsValue) -> Result<Vec<u8>, JsValue> {
+    let followers: IndexSet<String> = followers_from_js(followers_js, "followers for serialization")?
+        .into_iter()
+        .collect();
+    encode_mpack(&followers.into_iter().collect::<Vec<String>>())
+}
+
+/// A scraped follower list annotated with whether the scraper reached the end.
+///
+/// When `complete` is `false`, the scraper knows it stopped early (for example
+/// because Instagram rate-limited the modal), so accounts missing from
+/// `followers` are not necessarily gone.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PartialSnapshot {
+    pub complete: bool,
+    pub followers: Vec<String>,
+}
+
+/// The shapes accepted for a freshly scraped follower list. A plain array is
+/// treated as a complete snapshot.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NewFollowersInput {
+    List(Vec<String>),
+    Snapshot(PartialSnapshot),
+}
+
+/// Finds users who are in the new follower list but not in the old one.
+///
+/// Gained followers are still reliable when a scrape stopped early: every
+/// account it did capture is genuinely present. This makes it the function to
+/// use for `PartialSnapshot`s marked incomplete, where `find_unfollowers`
+/// reports no unfollowers at all.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers, or a `PartialSnapshot` object.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the new followers.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_new_followers(new_followers_js: JsValue, old_followers_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let old_set: HashSet<String> = decode_followers(old_followers_mpack, "old followers")?.into_iter().collect();
+    let new_set: HashSet<String> = new_snapshot_from_js(new_followers_js, "new followers")?.followers.into_iter().collect();
+
+    to_js(&sorted(new_set.difference(&old_set).cloned().collect()))
+}
+
+/// Both directions of a follower diff.
+///
+/// `retained` lists the followers present in both snapshots. It is only
+/// populated when explicitly requested, since for most accounts it is by far
+/// the largest of the three lists.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FollowerDiff {
+    pub unfollowers: Vec<String>,
+    pub new_followers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retained: Option<Vec<String>>,
+}
+
+impl FollowerDiff {
+    /// Computes the diff between two follower sets, with sorted lists.
+    pub(crate) fn between(old_set: &HashSet<String>, new_set: &HashSet<String>, include_retained: bool) -> Self {
+        FollowerDiff {
+            unfollowers: sorted(old_set.difference(new_set).cloned().collect()),
+            new_followers: sorted(new_set.difference(old_set).cloned().collect()),
+            retained: include_retained.then(|| sorted(old_set.intersection(new_set).cloned().collect())),
+        }
+    }
+}
+
+/// Computes the follower diff, optionally including the retained followers.
+///
+/// `retained` together with `new_followers` makes up the new list, and
+/// `retained` together with `unfollowers` makes up the old list, so a UI can
+/// show a "still following you" list alongside the changes.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `include_retained`: Whether to compute the `retained` list.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object mirroring `FollowerDiff`. The `retained`
+///   property is absent unless `include_retained` is `true`.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn diff_followers(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    include_retained: bool,
+) -> Result<JsValue, JsValue> {
+    let (old_set, new_set) = diff_sets(new_followers_js, old_followers_mpack)?;
+    to_js(&FollowerDiff::between(&old_set, &new_set, include_retained))
+}
+
+/// The result of `diff_with_stats`: both sides of the follower diff plus
+/// counters describing the inputs that produced it.
+///
+/// The duplicate counters report how many repeated usernames were collapsed
+/// from each list before comparing. A non-zero value usually means the scraper
+/// captured the same entry twice, which is worth surfacing even though the
+/// diff itself is unaffected.
+#[derive(Debug, Serialize)]
+pub struct DiffStats {
+    pub unfollowers: Vec<String>,
+    pub new_followers: Vec<String>,
+    pub old_count: usize,
+    pub new_count: usize,
+    pub old_duplicates_removed: usize,
+    pub new_duplicates_removed: usize,
+}
+
+/// Computes the full follower diff together with statistics about the inputs.
+///
+/// Unlike `find_unfollowers`, this function reports both directions of the diff
+/// (accounts lost and accounts gained), sorted alphabetically, along with the
+/// number of unique followers on each side and how many duplicate entries were
+/// collapsed while building the sets.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object mirroring `DiffStats`.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn diff_with_stats(new_followers_js: JsValue, old_followers_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let old_followers = decode_followers(old_followers_mpack, "old followers")?;
+    let new_followers = followers_from_js(new_followers_js, "new followers")?;
+
+    let old_total = old_followers.len();
+    let new_total = new_followers.len();
+    let old_set: HashSet<String> = old_followers.into_iter().collect();
+    let new_set: HashSet<String> = new_followers.into_iter().collect();
+
+    let stats = DiffStats {
+        unfollowers: sorted(old_set.difference(&new_set).cloned().collect()),
+        new_followers: sorted(new_set.difference(&old_set).cloned().collect()),
+        old_count: old_set.len(),
+        new_count: new_set.len(),
+        old_duplicates_removed: old_total - old_set.len(),
+        new_duplicates_removed: new_total - new_set.len(),
+    };
+
+    to_js(&stats)
+}
+
+/// The result of `reconcile`: how a stored blob differs from the expected one.
+#[derive(Debug, Serialize)]
+pub struct Reconciliation {
+    pub missing_in_actual: Vec<String>,
+    pub extra_in_actual: Vec<String>,
+    pub matches: bool,
+}
+
+/// Compares a stored follower blob against a known-good source of truth.
+///
+/// This is a symmetric difference with direction labels, meant for debugging
+/// storage corruption: `missing_in_actual` lists usernames that should be in
+/// the stored blob but are not, and `extra_in_actual` lists usernames the
+/// stored blob contains that it should not. Ordering and duplicates are
+/// ignored.
+///
+/// # Arguments
+///
+/// * `expected_mpack`: A byte slice (`&[u8]`) containing the reference follower
+///   list, serialized in MessagePack format.
+/// * `actual_mpack`: A byte slice (`&[u8]`) containing the follower list to verify.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object mirroring `Reconciliation`, with both
+///   lists sorted.
+/// - `Err(JsValue)`: A JavaScript error object if either blob cannot be deserialized.
+#[wasm_bindgen]
+pub fn reconcile(expected_mpack: &[u8], actual_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let expected: HashSet<String> = decode_followers(expected_mpack, "expected followers")?.into_iter().collect();
+    let actual: HashSet<String> = decode_followers(actual_mpack, "actual followers")?.into_iter().collect();
+
+    let missing_in_actual = sorted(expected.difference(&actual).cloned().collect());
+    let extra_in_actual = sorted(actual.difference(&expected).cloned().collect());
+    let matches = missing_in_actual.is_empty() && extra_in_actual.is_empty();
+
+    to_js(&Reconciliation { missing_in_actual, extra_in_actual, matches })
+}
+
+/// Finds unfollowers and returns them as a single string joined by `separator`.
+///
+/// For very large lists, marshalling one string across the WASM boundary is
+/// much cheaper than marshalling an array with thousands of elements; the
+/// caller can split it once on the JS side. Usernames are sorted.
+///
+/// Because the result is split on `separator`, no username may contain it.
+/// Such usernames are rejected with an error instead of silently producing a
+/// result that splits incorrectly.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `separator`: The non-empty string placed between usernames, e.g. `"\n"`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(String)`: The joined unfollowers; an empty string if there are none.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails, the
+///   separator is empty, or an unfollower's username contains the separator.
+#[wasm_bindgen]
+pub fn find_unfollowers_joined(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    separator: &str,
+) -> Result<String, JsValue> {
+    if separator.is_empty() {
+        return Err(JsValue::from_str("Separator must not be empty"));
+    }
+    let (old_set, new_set) = diff_sets(new_followers_js, old_followers_mpack)?;
+    let unfollowers = sorted(old_set.difference(&new_set).cloned().collect());
+
+    if let Some(username) = unfollowers.iter().find(|u| u.contains(separator)) {
+        return Err(JsValue::from_str(&format!(
+            "Username '{}' contains the separator {:?}",
+            username, separator
+        )));
+    }
+
+    Ok(unfollowers.join(separator))
+}
+
+/// Counts followers by the first character of their username.
+///
+/// Letters are lowercased, so `Alice` and `adam` share the `"a"` bucket. Any
+/// username that does not start with a letter (digits, `_`, `.`) is counted
+/// under `"#"`.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing a follower list serialized in
+///   MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object mapping each bucket to its follower count.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn follower_alpha_distribution(mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let followers: HashSet<String> = decode_followers(mpack, "followers")?.into_iter().collect();
+
+    let mut distribution: BTreeMap<String, usize> = BTreeMap::new();
+    for username in &followers {
+        let bucket = match username.chars().next() {
+            Some(first) if first.is_alphabetic() => first.to_lowercase().collect(),
+            _ => "#".to_string(),
+        };
+        *distribution.entry(bucket).or_insert(0) += 1;
+    }
+
+    to_js(&distribution)
+}
+
+/// Computes the fraction of a cohort of followers that is still following.
+///
+/// Given the usernames that followed during some past period (for example,
+/// everyone gained in January), this answers "what share of them stuck
+/// around?" by checking how many are present in the current snapshot.
+///
+/// # Arguments
+///
+/// * `cohort_js`: A `JsValue` from JavaScript, expected to be an array of strings with
+///   the cohort's usernames. Duplicates are counted once.
+/// * `current_followers_mpack`: A byte slice (`&[u8]`) containing the current follower
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(f64)`: The retained fraction, from `0.0` to `1.0`. An empty cohort returns
+///   `1.0`, since nobody in it has left.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn retention_of_cohort(cohort_js: JsValue, current_followers_mpack: &[u8]) -> Result<f64, JsValue> {
+    let cohort: HashSet<String> = followers_from_js(cohort_js, "cohort")?.into_iter().collect();
+    let current: HashSet<String> = decode_followers(current_followers_mpack, "current followers")?.into_iter().collect();
+
+    if cohort.is_empty() {
+        return Ok(1.0);
+    }
+    let retained = cohort.iter().filter(|username| current.contains(*username)).count();
+    Ok(retained as f64 / cohort.len() as f64)
+}
+
+/// Finds mutuals: accounts the user follows that also follow the user back.
+///
+/// # Arguments
+///
+/// * `following_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   with the accounts the user follows.
+/// * `followers_mpack`: A byte slice (`&[u8]`) containing the follower list,
+///   serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the mutuals.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn find_mutuals(following_js: JsValue, followers_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let (following, followers) = mutual_sets(following_js, followers_mpack)?;
+    to_js(&sorted(following.intersection(&followers).cloned().collect()))
+}
+
+/// Counts mutuals without materializing them.
+///
+/// Returns the same number as `find_mutuals(...).length`, but only iterates the
+/// smaller set against the larger one and never builds the result list, which
+/// avoids allocating and marshalling usernames when only the count is shown.
+///
+/// # Arguments
+///
+/// * `following_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   with the accounts the user follows.
+/// * `followers_mpack`: A byte slice (`&[u8]`) containing the follower list,
+///   serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(usize)`: The number of mutuals.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn mutual_count(following_js: JsValue, followers_mpack: &[u8]) -> Result<usize, JsValue> {
+    let (following, followers) = mutual_sets(following_js, followers_mpack)?;
+    let (smaller, larger) = if following.len() <= followers.len() {
+        (&following, &followers)
+    } else {
+        (&followers, &following)
+    };
+    Ok(smaller.iter().filter(|username| larger.contains(*username)).count())
+}
+
+/// Loads the following list and the follower blob as sets.
+fn mutual_sets(following_js: JsValue, followers_mpack: &[u8]) -> Result<(HashSet<String>, HashSet<String>), JsValue> {
+    let following = followers_from_js(following_js, "following")?.into_iter().collect();
+    let followers = decode_followers(followers_mpack, "followers")?.into_iter().collect();
+    Ok((following, followers))
+}
+
+/// A preview of the unfollowers: the true total plus the first few names.
+#[derive(Debug, Serialize)]
+pub struct CappedUnfollowers {
+    pub total: usize,
+    pub shown: Vec<String>,
+}
+
+/// Finds unfollowers but returns at most `max` of them, together with the
+/// true total.
+///
+/// This is meant for previews such as a notification that shows "alice, bob
+/// and 48 others": only the names that will be displayed cross the WASM
+/// boundary.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `max`: The largest number of usernames to return.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ total, shown }`, where `shown` holds the
+///   alphabetically first `max` unfollowers.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_capped(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    max: usize,
+) -> Result<JsValue, JsValue> {
+    let (old_set, new_set) = diff_sets(new_followers_js, old_followers_mpack)?;
+    let mut unfollowers: Vec<String> = old_set.difference(&new_set).cloned().collect();
+    let total = unfollowers.len();
+
+    // Only the first `max` names need to be fully sorted.
+    if max < total {
+        unfollowers.select_nth_unstable(max);
+        unfollowers.truncate(max);
+    }
+
+    to_js(&CappedUnfollowers { total, shown: sorted(unfollowers) })
+}
+
+/// Finds unfollowers that the user was not following back.
+///
+/// These are accounts that followed without reciprocity and then took the
+/// follow away. Unfollowers that the user currently follows are excluded.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `following_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   with the accounts the user currently follows.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the matching unfollowers.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_i_didnt_follow(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    following_js: JsValue,
+) -> Result<JsValue, JsValue> {
+    let old_set: HashSet<String> = decode_followers(old_followers_mpack, "old followers")?.into_iter().collect();
+    let new_set: HashSet<String> = followers_from_js(new_followers_js, "new followers")?.into_iter().collect();
+    let following_set: HashSet<String> = followers_from_js(following_js, "following")?.into_iter().collect();
+
+    let unfollowers: Vec<String> = old_set
+        .difference(&new_set)
+        .filter(|username| !following_set.contains(*username))
+        .cloned()
+        .collect();
+
+    to_js(&sorted(unfollowers))
+}
+
+/// Finds unfollowers, leaving out accounts in a per-call exclusion list.
+///
+/// Blocked or private accounts can vanish from a scrape without having
+/// unfollowed. The caller passes the accounts to disregard for this check
+/// only; nothing is persisted.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `exclusions_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   with the accounts to leave out of the result.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the remaining unfollowers.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_excluding(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    exclusions_js: JsValue,
+) -> Result<JsValue, JsValue> {
+    let (old_set, new_set) = diff_sets(new_followers_js, old_followers_mpack)?;
+    let exclusions: HashSet<String> = followers_from_js(exclusions_js, "exclusions")?.into_iter().collect();
+
+    let unfollowers: Vec<String> = old_set
+        .difference(&new_set)
+        .filter(|username| !exclusions.contains(*username))
+        .cloned()
+        .collect();
+
+    to_js(&sorted(unfollowers))
+}
+
+/// Finds fans the user recently dropped: accounts the user stopped following
+/// that still follow the user.
+///
+/// This composes two set operations: the accounts removed between the two
+/// following lists, intersected with the current followers. It surfaces
+/// relationships the user may want to reconsider.
+///
+/// # Arguments
+///
+/// * `prev_following_mpack`: A byte slice (`&[u8]`) containing the previous following
+///   list, serialized in MessagePack format.
+/// * `new_following_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   with the accounts the user follows now.
+/// * `followers_mpack`: A byte slice (`&[u8]`) containing the current follower list,
+///   serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the dropped fans.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_fans_i_dropped(
+    prev_following_mpack: &[u8],
+    new_following_js: JsValue,
+    followers_mpack: &[u8],
+) -> Result<JsValue, JsValue> {
+    let prev_following: HashSet<String> =
+        decode_followers(prev_following_mpack, "previous following")?.into_iter().collect();
+    let new_following: HashSet<String> = followers_from_js(new_following_js, "new following")?.into_iter().collect();
+    let followers: HashSet<String> = decode_followers(followers_mpack, "followers")?.into_iter().collect();
+
+    let dropped_fans: Vec<String> = prev_following
+        .difference(&new_following)
+        .filter(|username| followers.contains(*username))
+        .cloned()
+        .collect();
+
+    to_js(&sorted(dropped_fans))
+}
+
+/// A single change in a follower diff, tagged with its direction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FollowerEvent {
+    /// Either `"lost"` or `"gained"`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub username: String,
+}
+
+/// Expresses the follower diff as a single list of tagged events.
+///
+/// This suits UIs that render one unified activity feed instead of separate
+/// lost and gained sections.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of `{ type: "lost" | "gained", username }`
+///   objects, sorted by username.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn diff_as_events(new_followers_js: JsValue, old_followers_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let (old_set, new_set) = diff_sets(new_followers_js, old_followers_mpack)?;
+
+    let lost = old_set.difference(&new_set).map(|username| FollowerEvent { kind: "lost", username: username.clone() });
+    let gained = new_set.difference(&old_set).map(|username| FollowerEvent { kind: "gained", username: username.clone() });
+    let mut events: Vec<FollowerEvent> = lost.chain(gained).collect();
+    events.sort_by(|a, b| a.username.cmp(&b.username));
+
+    to_js(&events)
+}
+
+/// Checks whether a new follower list plausibly belongs to the same account as
+/// the previous one, before it is accepted and stored.
+///
+/// If the wrong logged-in user was scraped, the new list shares almost nothing
+/// with the stored one. The size of the symmetric difference (accounts gained
+/// plus accounts lost) is compared to the size of the previous list; above
+/// `max_delta_ratio`, the new list is rejected as a probable account switch.
+/// For example, with `max_delta_ratio = 0.5` an account with 1000 followers may
+/// gain and lose up to 500 accounts in total between two checks.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `max_delta_ratio`: The largest accepted ratio of changed accounts to the
+///   previous follower count.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(bool)`: Whether the new list is a plausible successor. Always `true` on the
+///   first run, when the previous list is empty.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn is_plausible_successor(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    max_delta_ratio: f64,
+) -> Result<bool, JsValue> {
+    let (old_set, new_set) = diff_sets(new_followers_js, old_followers_mpack)?;
+    if old_set.is_empty() {
+        return Ok(true);
+    }
+
+    let changed = old_set.symmetric_difference(&new_set).count();
+    Ok(changed as f64 / old_set.len() as f64 <= max_delta_ratio)
+}
+
+/// Finds repeat offenders: current unfollowers who have unfollowed before.
+///
+/// # Arguments
+///
+/// * `current_unfollowers_js`: A `JsValue` from JavaScript, expected to be an array of
+///   strings with the unfollowers from the latest check.
+/// * `past_unfollowers_mpack`: A byte slice (`&[u8]`) containing the accounts that
+///   unfollowed in earlier checks, serialized in MessagePack format.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the accounts found in
+///   both lists.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_repeat_unfollowers(current_unfollowers_js: JsValue, past_unfollowers_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let past: HashSet<String> = decode_followers(past_unfollowers_mpack, "past unfollowers")?.into_iter().collect();
+    let current: HashSet<String> = followers_from_js(current_unfollowers_js, "current unfollowers")?.into_iter().collect();
+
+    to_js(&sorted(current.intersection(&past).cloned().collect()))
+}
+
+/// The profile URL prefix used by `find_unfollowers_as_urls` when none is given.
+const DEFAULT_PROFILE_BASE_URL: &str = "https://instagram.com/";
+
+/// Finds unfollowers and returns them as clickable profile URLs.
+///
+/// Each unfollower becomes `base_url` followed by the username. `base_url`
+/// must be an `http://` or `https://` URL ending in `/`, and every username
+/// must consist of URL-safe characters (ASCII letters, digits, `.`, `_`, `-`
+/// and `~`), which all real Instagram handles do. Anything else is rejected
+/// rather than producing a broken or misleading link.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `base_url`: An optional URL prefix. Defaults to `https://instagram.com/`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of profile URL strings, sorted by username.
+/// - `Err(JsValue)`: A JavaScript error object if `base_url` or an unfollower's
+///   username is not URL-safe, or if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_as_urls(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    base_url: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let base_url = base_url.unwrap_or_else(|| DEFAULT_PROFILE_BASE_URL.to_string());
+    if !(base_url.starts_with("https://") || base_url.starts_with("http://")) || !base_url.ends_with('/') {
+        return Err(JsValue::from_str(&format!(
+            "Invalid base URL '{}': expected an http:// or https:// URL ending in '/'",
+            base_url
+        )));
+    }
+
+    let (old_set, new_set) = diff_sets(new_followers_js, old_followers_mpack)?;
+    let unfollowers = sorted(old_set.difference(&new_set).cloned().collect());
+
+    let urls = unfollowers
+        .into_iter()
+        .map(|username| {
+            let url_safe = !username.is_empty()
+                && username.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-' | b'~'));
+            if url_safe {
+                Ok(format!("{}{}", base_url, username))
+            } else {
+                Err(JsValue::from_str(&format!("Username '{}' is not URL-safe", username)))
+            }
+        })
+        .collect::<Result<Vec<String>, JsValue>>()?;
+
+    to_js(&urls)
+}
+
+/// How many elements are processed between two calls to a cancellation callback.
+const CANCEL_CHECK_INTERVAL: usize = 4096;
+
+/// Finds unfollowers like `find_unfollowers`, but can be stopped part-way.
+///
+/// `should_cancel` is invoked before the work starts and then after every few
+/// thousand usernames processed. As soon as it returns a truthy value the
+/// function gives up and returns a `TrackerError::Cancelled` error instead of a
+/// partial result, which keeps a worker that may be shut down responsive even
+/// during a very large diff.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `should_cancel`: A JavaScript function taking no arguments that returns a truthy
+///   value when the operation should stop.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the unfollowers.
+/// - `Err(JsValue)`: A JavaScript error object if the operation was cancelled, if
+///   `should_cancel` throws, or if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn find_unfollowers_cancellable(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    should_cancel: &Function,
+) -> Result<JsValue, JsValue> {
+    let check_cancelled = || -> Result<(), JsValue> {
+        if should_cancel.call0(&JsValue::NULL)?.is_truthy() {
+            return Err(TrackerError::Cancelled.into());
+        }
+        Ok(())
+    };
+
+    check_cancelled()?;
+    let old_followers = decode_followers(old_followers_mpack, "old followers")?;
+    let new_followers = followers_from_js(new_followers_js, "new followers")?;
+
+    let mut new_set: HashSet<String> = HashSet::with_capacity(new_followers.len());
+    for (i, username) in new_followers.into_iter().enumerate() {
+        if i % CANCEL_CHECK_INTERVAL == 0 {
+            check_cancelled()?;
+        }
+        new_set.insert(username);
+    }
+
+    let mut unfollowers: HashSet<String> = HashSet::new();
+    for (i, username) in old_followers.into_iter().enumerate() {
+        if i % CANCEL_CHECK_INTERVAL == 0 {
+            check_cancelled()?;
+        }
+        if !new_set.contains(&username) {
+            unfollowers.insert(username);
+        }
+    }
+
+    to_js(&sorted(unfollowers.into_iter().collect()))
+}
+
+// --- Internal helpers ---
+// Shared plumbing for the exported functions above. They keep the error
+// messages consistent across the JS boundary.
+
+/// Deserializes a MessagePack follower list. An empty slice is treated as an
+/// empty list, which is what the extension passes on the very first run. A map
+/// keyed by username is accepted as well as an array.
+pub(crate) fn decode_followers(mpack: &[u8], what: &str) -> Result<Vec<String>, JsValue> {
+    decode_layout(mpack, what, BlobFormat::Usernames).map(|UsernameList(usernames)| usernames)
+}
+
+/// Deserializes a MessagePack list of records of any type, treating an empty
+/// slice as an empty list.
+pub(crate) fn decode_records<T: DeserializeOwned>(mpack: &[u8], what: &str) -> Result<Vec<T>, JsValue> {
+    decode_mpack(mpack, what)
+}
+
+/// Deserializes a MessagePack value of any type, treating an empty slice as
+/// the type's default (an empty list, an empty snapshot, ...).
+pub(crate) fn decode_mpack<T: DeserializeOwned + Default>(mpack: &[u8], what: &str) -> Result<T, JsValue> {
+    if mpack.is_empty() {
+        return Ok(T::default());
+    }
+    rmp_serde::from_slice(mpack).map_err(|e| {
+        TrackerError::BlobDecode { what: what.to_string(), bytes: mpack.len(), detail: e.to_string() }.into()
+    })
+}
+
+/// Deserializes the new follower input, which may be a plain array or a
+/// `PartialSnapshot` object.
+fn new_snapshot_from_js(value: JsValue, what: &str) -> Result<PartialSnapshot, JsValue> {
+    let value = collection_to_array(value);
+    let elements = js_array_len(&value);
+    let input: NewFollowersInput = serde_wasm_bindgen::from_value(value).map_err(|e| TrackerError::InputDecode {
+        what: what.to_string(),
+        elements,
+        detail: format!("expected an array of strings or {{ complete, followers }}: {}", e),
+    })?;
+    let snapshot = match input {
+        NewFollowersInput::List(followers) => PartialSnapshot { complete: true, followers },
+        NewFollowersInput::Snapshot(snapshot) => snapshot,
+    };
+    validate_usernames(&snapshot.followers)?;
+    Ok(snapshot)
+}
+
+/// Loads the two sides of a follower diff as sets: the previous list from its
+/// MessagePack blob and the latest list from JavaScript.
+pub(crate) fn diff_sets(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+) -> Result<(HashSet<String>, HashSet<String>), JsValue> {
+    let old_set = decode_followers(old_followers_mpack, "old followers")?.into_iter().collect();
+    let new_set = followers_from_js(new_followers_js, "new followers")?.into_iter().collect();
+    Ok((old_set, new_set))
+}
+
+/// Deserializes a JavaScript collection of usernames into a Rust vector,
+/// rejecting over-long usernames. Arrays, `Set`s and `Map`s (by key) are
+/// accepted; anything else is reported with a clear error.
+pub(crate) fn followers_from_js(value: JsValue, what: &str) -> Result<Vec<String>, JsValue> {
+    let value = collection_to_array(value);
+    if !Array::is_array(&value) {
+        return Err(TrackerError::InputDecode {
+            what: what.to_string(),
+            elements: None,
+            detail: format!(
+                "expected an Array, Set or Map of usernames, found {}",
+                value.js_typeof().as_string().unwrap_or_default()
+            ),
+        }
+        .into());
+    }
+    let followers = records_from_js(value, what)?;
+    validate_usernames(&followers)?;
+    Ok(followers)
+}
+
+/// Converts a JavaScript `Set` into an array of its values and a `Map` into an
+/// array of its keys, so either can be passed where an array is expected.
+/// Other values are returned unchanged.
+pub(crate) fn collection_to_array(value: JsValue) -> JsValue {
+    if let Some(map) = value.dyn_ref::<Map>() {
+        Array::from(&map.keys()).into()
+    } else if value.is_instance_of::<Set>() {
+        Array::from(&value).into()
+    } else {
+        value
+    }
+}
+
+/// Deserializes a JavaScript array (or `Set`) of records of any type into a
+/// Rust vector.
+pub(crate) fn records_from_js<T: DeserializeOwned>(value: JsValue, what: &str) -> Result<Vec<T>, JsValue> {
+    let value = collection_to_array(value);
+    let elements = js_array_len(&value);
+    serde_wasm_bindgen::from_value(value).map_err(|e| {
+        TrackerError::InputDecode { what: what.to_string(), elements, detail: e.to_string() }.into()
+    })
+}
+
+/// Returns the length of a JavaScript array, or `None` for any other value.
+fn js_array_len(value: &JsValue) -> Option<usize> {
+    value.dyn_ref::<Array>().map(|array| array.length() as usize)
+}
+
+/// Serializes a value into MessagePack bytes.
+pub(crate) fn encode_mpack<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, JsValue> {
+    rmp_serde::to_vec(value)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize to MessagePack: {}", e)))
+}
+
+/// Serializes a result value into a `JsValue` for the caller. Maps become plain
+/// JavaScript objects rather than `Map` instances.
+pub(crate) fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+/// Removes every occurrence of the user's own handle, ignoring case.
+pub(crate) fn remove_own_username(followers: &mut Vec<String>, own_username: &str) {
+    let own = own_username.trim().to_lowercase();
+    followers.retain(|username| username.to_lowercase() != own);
+}
+
+/// Sorts a list of usernames so results are stable across calls.
+pub(crate) fn sorted(mut usernames: Vec<String>) -> Vec<String> {
+    usernames.sort_unstable();
+    usernames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(items: &[&str]) -> HashSet<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn retained_completes_both_sides_of_the_diff() {
+        let old_set = set(&["alice", "bob", "carol"]);
+        let new_set = set(&["carol", "alice", "dave", "erin"]);
+
+        let diff = FollowerDiff::between(&old_set, &new_set, true);
+        let retained = diff.retained.clone().unwrap();
+        assert_eq!(retained, ["alice", "carol"]);
+
+        let with_gained: HashSet<String> = retained.iter().chain(&diff.new_followers).cloned().collect();
+        let with_lost: HashSet<String> = retained.iter().chain(&diff.unfollowers).cloned().collect();
+        assert_eq!(with_gained, new_set);
+        assert_eq!(with_lost, old_set);
+        assert_eq!(retained.len() + diff.new_followers.len(), new_set.len());
+        assert_eq!(retained.len() + diff.unfollowers.len(), old_set.len());
+    }
+
+    #[test]
+    fn retained_is_only_computed_on_request() {
+        let diff = FollowerDiff::between(&set(&["alice"]), &set(&["alice"]), false);
+        assert_eq!(diff.retained, None);
+    }
+
+    #[test]
+    fn own_username_is_removed_ignoring_case() {
+        let mut followers: Vec<String> = ["alice", "Me_Myself", "bob", "me_myself"].map(String::from).to_vec();
+        remove_own_username(&mut followers, " me_MYSELF ");
+        assert_eq!(followers, ["alice", "bob"]);
+    }
+}
@@ -0,0 +1,91 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: notes.rs
+* Description: Personal notes on followers. A `NotedSnapshot` stores the
+* latest `Follower` records together with free-text notes keyed by `user_id`
+* (e.g. "met at conference"). Because notes are keyed by the immutable id
+* rather than the handle, they survive renames as well as snapshot updates.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::error::validate_username;
+use crate::follower::Follower;
+use crate::{decode_mpack, encode_mpack, records_from_js};
+
+/// A snapshot of `Follower` records with personal notes attached.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotedSnapshot {
+    pub followers: Vec<Follower>,
+    /// Notes keyed by the follower's `user_id`, written in decimal.
+    #[serde(default)]
+    pub notes: HashMap<String, String>,
+}
+
+/// Replaces the followers in a noted snapshot with the latest scrape while
+/// carrying the notes forward.
+///
+/// Notes for accounts that are still present are kept. Notes for accounts that
+/// have left are dropped, unless `keep_departed_notes` is set, in which case
+/// they are kept so they reappear if the account follows again.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `Follower` objects representing the latest list of followers.
+/// * `old_snapshot_mpack`: A byte slice (`&[u8]`) containing the previous noted
+///   snapshot. An empty slice starts a new snapshot without notes.
+/// * `keep_departed_notes`: Whether to keep notes for accounts no longer present.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The updated noted snapshot, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn update_followers_preserving_notes(
+    new_followers_js: JsValue,
+    old_snapshot_mpack: &[u8],
+    keep_departed_notes: bool,
+) -> Result<Vec<u8>, JsValue> {
+    let mut notes = decode_noted_snapshot(old_snapshot_mpack)?.notes;
+    let followers: Vec<Follower> = records_from_js(new_followers_js, "new follower records")?;
+    followers.iter().try_for_each(|follower| validate_username(&follower.username))?;
+
+    if !keep_departed_notes {
+        let present: HashSet<String> = followers.iter().map(|follower| follower.user_id.to_string()).collect();
+        notes.retain(|user_id, _| present.contains(user_id));
+    }
+
+    encode_mpack(&NotedSnapshot { followers, notes })
+}
+
+/// Attaches a note to a follower in a noted snapshot, or removes it.
+///
+/// # Arguments
+///
+/// * `snapshot_mpack`: A byte slice (`&[u8]`) containing the noted snapshot.
+/// * `user_id`: The follower's `user_id`, written in decimal.
+/// * `note`: The note text, or `undefined`/`null` to remove the existing note.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The updated noted snapshot, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or serialization fails.
+#[wasm_bindgen]
+pub fn set_follower_note(snapshot_mpack: &[u8], user_id: &str, note: Option<String>) -> Result<Vec<u8>, JsValue> {
+    let mut snapshot = decode_noted_snapshot(snapshot_mpack)?;
+    match note {
+        Some(note) => snapshot.notes.insert(user_id.to_string(), note),
+        None => snapshot.notes.remove(user_id),
+    };
+
+    encode_mpack(&snapshot)
+}
+
+/// Deserializes a noted snapshot, treating an empty slice as an empty snapshot.
+fn decode_noted_snapshot(mpack: &[u8]) -> Result<NotedSnapshot, JsValue> {
+    decode_mpack(mpack, "noted snapshot")
+}
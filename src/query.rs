@@ -0,0 +1,168 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: query.rs
+* Description: A tiny query language for ad-hoc follower filtering. A query
+* is one or more whitespace-separated terms such as `startswith:john`,
+* `contains:official`, `len>15` or `verified:true`; a follower matches when
+* every term matches. Queries are parsed up front so that a typo is reported
+* as an error instead of silently matching nothing.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::follower::decode_follower_records;
+use crate::format::decode_any_followers;
+use crate::to_js;
+
+/// A single parsed query term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    /// `startswith:<prefix>`, case-insensitive.
+    StartsWith(String),
+    /// `contains:<text>`, case-insensitive.
+    Contains(String),
+    /// `len>N`, in characters.
+    LongerThan(usize),
+    /// `len<N`, in characters.
+    ShorterThan(usize),
+    /// `len=N`, in characters.
+    LengthIs(usize),
+    /// `verified:true` or `verified:false`. Needs `Follower` records.
+    Verified(bool),
+}
+
+impl Term {
+    fn parse(term: &str) -> Result<Self, JsValue> {
+        if let Some(prefix) = term.strip_prefix("startswith:") {
+            return Ok(Term::StartsWith(prefix.to_lowercase()));
+        }
+        if let Some(text) = term.strip_prefix("contains:") {
+            return Ok(Term::Contains(text.to_lowercase()));
+        }
+        if let Some(value) = term.strip_prefix("verified:") {
+            return match value {
+                "true" => Ok(Term::Verified(true)),
+                "false" => Ok(Term::Verified(false)),
+                other => Err(JsValue::from_str(&format!(
+                    "Invalid query term '{}': expected verified:true or verified:false, found '{}'",
+                    term, other
+                ))),
+            };
+        }
+        if let Some(rest) = term.strip_prefix("len") {
+            let mut chars = rest.chars();
+            let op = chars.next();
+            let length: Option<usize> = chars.as_str().parse().ok();
+            return match (op, length) {
+                (Some('>'), Some(n)) => Ok(Term::LongerThan(n)),
+                (Some('<'), Some(n)) => Ok(Term::ShorterThan(n)),
+                (Some('='), Some(n)) => Ok(Term::LengthIs(n)),
+                _ => Err(JsValue::from_str(&format!(
+                    "Invalid query term '{}': expected len>N, len<N or len=N",
+                    term
+                ))),
+            };
+        }
+        Err(JsValue::from_str(&format!(
+            "Unknown query term '{}': expected startswith:, contains:, len>N, len<N, len=N or verified:",
+            term
+        )))
+    }
+
+    fn matches(&self, username: &str, is_verified: bool) -> bool {
+        match self {
+            Term::StartsWith(prefix) => username.to_lowercase().starts_with(prefix.as_str()),
+            Term::Contains(text) => username.to_lowercase().contains(text.as_str()),
+            Term::LongerThan(n) => username.chars().count() > *n,
+            Term::ShorterThan(n) => username.chars().count() < *n,
+            Term::LengthIs(n) => username.chars().count() == *n,
+            Term::Verified(verified) => is_verified == *verified,
+        }
+    }
+}
+
+/// Filters a follower blob with a small query language.
+///
+/// The query is one or more whitespace-separated terms, all of which must
+/// match:
+/// - `startswith:<prefix>` and `contains:<text>` match the username,
+///   ignoring case.
+/// - `len>N`, `len<N` and `len=N` compare the username's length in characters.
+/// - `verified:true` and `verified:false` match the verified badge. They need a
+///   blob of `Follower` records; accounts whose badge was not captured count
+///   as not verified.
+///
+/// For example, `startswith:john len>8` finds `johnathan_s` but not `johnny`.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing the follower blob, serialized in
+///   MessagePack format.
+/// * `query`: The query string.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings with the matching usernames.
+/// - `Err(JsValue)`: A JavaScript error object if the query is empty or contains an
+///   unknown or malformed term, if it filters on data the blob does not contain,
+///   or if deserialization fails.
+#[wasm_bindgen]
+pub fn filter_followers(mpack: &[u8], query: &str) -> Result<JsValue, JsValue> {
+    let terms: Vec<Term> = query.split_whitespace().map(Term::parse).collect::<Result<_, _>>()?;
+    if terms.is_empty() {
+        return Err(JsValue::from_str("Query is empty: expected at least one term"));
+    }
+
+    let needs_records = terms.iter().any(|term| matches!(term, Term::Verified(_)));
+    let followers: Vec<(String, bool)> = if needs_records {
+        decode_follower_records(mpack, "follower records")?
+            .into_iter()
+            .map(|follower| (follower.username, follower.is_verified.unwrap_or(false)))
+            .collect()
+    } else {
+        let (_, usernames) = decode_any_followers(mpack, "followers")?;
+        usernames.into_iter().map(|username| (username, false)).collect()
+    };
+
+    let matching: BTreeSet<String> = followers
+        .into_iter()
+        .filter(|(username, is_verified)| terms.iter().all(|term| term.matches(username, *is_verified)))
+        .map(|(username, _)| username)
+        .collect();
+
+    to_js(&matching)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(text: &str) -> Term {
+        Term::parse(text).unwrap()
+    }
+
+    #[test]
+    fn parses_each_operator() {
+        assert_eq!(term("startswith:John"), Term::StartsWith("john".to_string()));
+        assert_eq!(term("contains:Official"), Term::Contains("official".to_string()));
+        assert_eq!(term("len>15"), Term::LongerThan(15));
+        assert_eq!(term("len<4"), Term::ShorterThan(4));
+        assert_eq!(term("len=6"), Term::LengthIs(6));
+        assert_eq!(term("verified:true"), Term::Verified(true));
+        assert_eq!(term("verified:false"), Term::Verified(false));
+    }
+
+    #[test]
+    fn each_operator_matches_as_documented() {
+        assert!(term("startswith:john").matches("JohnSmith", false));
+        assert!(!term("startswith:john").matches("big_john", false));
+        assert!(term("contains:official").matches("nasa_Official_", false));
+        assert!(term("len>5").matches("björk_", false));
+        assert!(!term("len>5").matches("björk", false));
+        assert!(term("len<6").matches("björk", false));
+        assert!(term("len=5").matches("björk", false));
+        assert!(term("verified:true").matches("anyone", true));
+        assert!(!term("verified:true").matches("anyone", false));
+    }
+}
@@ -0,0 +1,193 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: rename.rs
+* Description: Rename detection for username-only snapshots. When an account
+* changes its handle, a plain diff reports it as one unfollower plus one new
+* follower. This module pairs up unfollowers and new followers whose
+* usernames are within a small Levenshtein distance of each other and
+* reports them as likely renames instead, reducing false positives when no
+* `user_id` is available to tell the accounts apart.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::{decode_followers, followers_from_js, sorted, to_js};
+
+/// A lost and a gained username that are probably the same account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Rename {
+    pub from: String,
+    pub to: String,
+}
+
+/// The result of `diff_with_rename_detection`.
+///
+/// Usernames that were paired as a rename are listed only in `likely_renames`
+/// and are removed from `unfollowers` and `new_followers`.
+#[derive(Debug, Serialize)]
+pub struct RenameAwareDiff {
+    pub unfollowers: Vec<String>,
+    pub new_followers: Vec<String>,
+    pub likely_renames: Vec<Rename>,
+}
+
+/// Computes the follower diff, pairing similar lost and gained usernames as
+/// likely renames.
+///
+/// Candidate pairs are matched greedily from the closest edit distance up, so
+/// each username takes part in at most one rename. Ties are broken
+/// alphabetically to keep the result stable.
+///
+/// Only usernames whose lengths differ by at most `max_distance` are compared.
+/// If that still leaves more than `MAX_RENAME_COMPARISONS` pairs, as after a
+/// mass unfollow or a follow spree, rename detection is skipped and the plain
+/// diff is returned with no `likely_renames`, rather than blocking the thread.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_followers_mpack`: A byte slice (`&[u8]`) containing the previous follower
+///   list, serialized in MessagePack format.
+/// * `max_distance`: The largest Levenshtein distance (in characters) for which a
+///   lost and a gained username are considered the same account.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object mirroring `RenameAwareDiff`.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn diff_with_rename_detection(
+    new_followers_js: JsValue,
+    old_followers_mpack: &[u8],
+    max_distance: usize,
+) -> Result<JsValue, JsValue> {
+    let old_set: HashSet<String> = decode_followers(old_followers_mpack, "old followers")?.into_iter().collect();
+    let new_set: HashSet<String> = followers_from_js(new_followers_js, "new followers")?.into_iter().collect();
+
+    let lost: Vec<&String> = old_set.difference(&new_set).collect();
+    let gained: Vec<&String> = new_set.difference(&old_set).collect();
+
+    let renames = pair_renames(&lost, &gained, max_distance);
+    let paired_from: HashSet<&str> = renames.iter().map(|(from, _)| from.as_str()).collect();
+    let paired_to: HashSet<&str> = renames.iter().map(|(_, to)| to.as_str()).collect();
+    let mut likely_renames: Vec<Rename> =
+        renames.iter().map(|&(from, to)| Rename { from: from.clone(), to: to.clone() }).collect();
+    likely_renames.sort_by(|a, b| a.from.cmp(&b.from));
+
+    let diff = RenameAwareDiff {
+        unfollowers: sorted(lost.into_iter().filter(|u| !paired_from.contains(u.as_str())).cloned().collect()),
+        new_followers: sorted(gained.into_iter().filter(|u| !paired_to.contains(u.as_str())).cloned().collect()),
+        likely_renames,
+    };
+
+    to_js(&diff)
+}
+
+/// The most lost/gained username pairs `diff_with_rename_detection` compares
+/// before giving up on rename detection.
+pub const MAX_RENAME_COMPARISONS: usize = 100_000;
+
+/// Pairs lost and gained usernames within `max_distance` edits, closest first.
+/// Returns no pairs when more than `MAX_RENAME_COMPARISONS` would be compared.
+fn pair_renames<'a>(
+    lost: &[&'a String],
+    gained: &[&'a String],
+    max_distance: usize,
+) -> Vec<(&'a String, &'a String)> {
+    // Bucket the gained usernames by length: a pair whose lengths differ by
+    // more than `max_distance` can never be within `max_distance` edits.
+    let mut by_len: HashMap<usize, Vec<&String>> = HashMap::new();
+    for &to in gained {
+        by_len.entry(to.chars().count()).or_default().push(to);
+    }
+    let lost: Vec<(&String, usize)> = lost.iter().map(|&from| (from, from.chars().count())).collect();
+    let bucket_range = |len: usize| len.saturating_sub(max_distance)..=len.saturating_add(max_distance);
+
+    let comparisons: usize = lost
+        .iter()
+        .flat_map(|&(_, len)| bucket_range(len).filter_map(|len| by_len.get(&len)))
+        .map(Vec::len)
+        .sum();
+    if comparisons > MAX_RENAME_COMPARISONS {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<(usize, &String, &String)> = Vec::new();
+    for &(from, len) in &lost {
+        for &to in bucket_range(len).filter_map(|len| by_len.get(&len)).flatten() {
+            let distance = levenshtein(from, to);
+            if distance <= max_distance {
+                candidates.push((distance, from, to));
+            }
+        }
+    }
+    candidates.sort();
+
+    let mut paired_from: HashSet<&str> = HashSet::new();
+    let mut paired_to: HashSet<&str> = HashSet::new();
+    let mut pairs = Vec::new();
+    for (_, from, to) in candidates {
+        if paired_from.contains(from.as_str()) || paired_to.contains(to.as_str()) {
+            continue;
+        }
+        paired_from.insert(from);
+        paired_to.insert(to);
+        pairs.push((from, to));
+    }
+    pairs
+}
+
+/// Computes the Levenshtein edit distance between two strings, counted in
+/// characters rather than bytes.
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_counts_edits_in_characters() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("alice", ""), 5);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("jane.doe", "jane_doe_"), 2);
+        assert_eq!(levenshtein("café", "cafe"), 1);
+    }
+
+    #[test]
+    fn skips_detection_when_a_mass_change_needs_too_many_comparisons() {
+        let lost: Vec<String> = (0..2000).map(|i| format!("lost_user_{:05}", i)).collect();
+        let gained: Vec<String> = (0..2000).map(|i| format!("lost_usr_{:05}_", i)).collect();
+        let lost: Vec<&String> = lost.iter().collect();
+        let gained: Vec<&String> = gained.iter().collect();
+
+        // 4,000,000 same-length pairs is far above the cap: nothing is paired.
+        assert!(pair_renames(&lost, &gained, 2).is_empty());
+        // A small slice of the same data is still compared and paired.
+        assert_eq!(pair_renames(&lost[..1], &gained[..300], 2), [(lost[0], gained[0])]);
+    }
+
+    #[test]
+    fn only_compares_usernames_of_a_similar_length() {
+        let (from, near, far) = ("jane.doe".to_string(), "jane_doe_".to_string(), "jane.doe_the_second".to_string());
+        assert_eq!(pair_renames(&[&from], &[&far, &near], 2), [(&from, &near)]);
+    }
+}
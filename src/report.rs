@@ -0,0 +1,152 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: report.rs
+* Description: Shareable "report cards". A report card condenses the history
+* log into a handful of aggregate numbers (follower count, recent net change,
+* the worst single drop, all-time unfollowers) and contains no usernames, so
+* it can be shared or posted without exposing anyone. It is stored as a small
+* MessagePack blob.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::error::{validate_timestamp, TrackerError};
+use crate::history::{decode_history, HistoryEntry};
+use crate::{encode_mpack, to_js};
+
+const WEEK_MS: f64 = 7.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+const MONTH_MS: f64 = 30.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Aggregate follower statistics, free of usernames.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportCard {
+    /// When the card was generated, in milliseconds since the Unix epoch.
+    pub generated_at: f64,
+    /// The follower count at the latest check.
+    pub total_followers: usize,
+    /// The change in follower count over the last 7 days.
+    pub net_change_week: i64,
+    /// The change in follower count over the last 30 days.
+    pub net_change_month: i64,
+    /// The most unfollowers recorded by a single check.
+    pub biggest_drop: usize,
+    /// The number of distinct accounts that have ever unfollowed.
+    pub all_time_unfollowers: usize,
+}
+
+/// Condenses the history log into a shareable report card blob.
+///
+/// Net changes compare the latest check with the check closest to one week and
+/// one month (30 days) before `now`, the same way as `growth_rate`. If the log is
+/// younger than the period, its first check is the closest one.
+///
+/// # Arguments
+///
+/// * `history_mpack`: A byte slice (`&[u8]`) containing the history log.
+/// * `now`: The current time in milliseconds since the Unix epoch.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The report card, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if `now` is not a valid timestamp,
+///   or if the history is empty or cannot be deserialized.
+#[wasm_bindgen]
+pub fn generate_report_card(history_mpack: &[u8], now: f64) -> Result<Vec<u8>, JsValue> {
+    validate_timestamp("now", now)?;
+    let history = decode_history(history_mpack)?;
+
+    let latest = history
+        .iter()
+        .max_by(|a, b| a.timestamp.total_cmp(&b.timestamp))
+        .ok_or_else(|| JsValue::from_str("Cannot generate report card: history is empty"))?;
+    let net_change_since = |target: f64| {
+        let baseline = closest_entry(&history, target).unwrap_or(latest);
+        latest.follower_count as i64 - baseline.follower_count as i64
+    };
+
+    let departed: HashSet<&String> = history.iter().flat_map(|entry| &entry.unfollowers).collect();
+    let card = ReportCard {
+        generated_at: now,
+        total_followers: latest.follower_count,
+        net_change_week: net_change_since(now - WEEK_MS),
+        net_change_month: net_change_since(now - MONTH_MS),
+        biggest_drop: history.iter().map(|entry| entry.unfollowers.len()).max().unwrap_or(0),
+        all_time_unfollowers: departed.len(),
+    };
+
+    encode_mpack(&card)
+}
+
+/// Decodes a report card blob for display.
+///
+/// # Arguments
+///
+/// * `card_mpack`: A byte slice (`&[u8]`) containing a report card, as produced by
+///   `generate_report_card`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object with the `ReportCard` fields.
+/// - `Err(JsValue)`: A JavaScript error object if the blob is not a report card.
+#[wasm_bindgen]
+pub fn read_report_card(card_mpack: &[u8]) -> Result<JsValue, JsValue> {
+    let card: ReportCard = rmp_serde::from_slice(card_mpack).map_err(|e| TrackerError::BlobDecode {
+        what: "report card".to_string(),
+        bytes: card_mpack.len(),
+        detail: e.to_string(),
+    })?;
+    to_js(&card)
+}
+
+/// Finds the entry whose timestamp is closest to `target`.
+fn closest_entry(history: &[HistoryEntry], target: f64) -> Option<&HistoryEntry> {
+    history
+        .iter()
+        .min_by(|a, b| (a.timestamp - target).abs().total_cmp(&(b.timestamp - target).abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+    fn check(timestamp: f64, follower_count: usize, unfollowers: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            follower_count,
+            new_followers: Vec::new(),
+            unfollowers: unfollowers.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn report_card_round_trips_with_aggregates_only() {
+        let now = 40.0 * DAY_MS;
+        let history = vec![
+            check(0.0, 100, &[]),
+            check(10.0 * DAY_MS, 120, &["alice"]),
+            check(33.0 * DAY_MS, 130, &["bob", "carol", "alice"]),
+            check(now, 125, &["dave"]),
+        ];
+
+        let blob = generate_report_card(&rmp_serde::to_vec(&history).unwrap(), now).unwrap();
+        let card: ReportCard = rmp_serde::from_slice(&blob).unwrap();
+        assert_eq!(
+            card,
+            ReportCard {
+                generated_at: now,
+                total_followers: 125,
+                net_change_week: -5,
+                net_change_month: 5,
+                biggest_drop: 3,
+                all_time_unfollowers: 4,
+            }
+        );
+        let text = String::from_utf8_lossy(&blob);
+        assert!(!text.contains("alice") && !text.contains("dave"));
+    }
+}
@@ -0,0 +1,169 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: sampling.rs
+* Description: Deterministic, seeded randomness for spot-checking follower
+* lists. A small SplitMix64 generator is used instead of pulling in a full
+* RNG crate: it is fast, has good statistical quality for this purpose, and
+* produces the same sequence on every platform for a given seed, which keeps
+* samples and shuffles reproducible.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use std::collections::HashSet;
+
+use crate::follower::decode_follower_records;
+use crate::format::{detect_format, BlobFormat};
+use crate::{decode_followers, encode_mpack, to_js};
+
+/// A SplitMix64 pseudo-random number generator.
+///
+/// Not suitable for anything security-related; it exists purely to make
+/// sampling and shuffling reproducible from a seed.
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..bound`. `bound` must be non-zero.
+    pub(crate) fn next_below(&mut self, bound: usize) -> usize {
+        // The modulo bias is negligible for follower-list sized bounds.
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// Shuffles the first `n` positions of `items` using a partial Fisher-Yates
+/// shuffle, so that `items[..n]` is a uniform sample without replacement.
+pub(crate) fn partial_shuffle<T>(items: &mut [T], n: usize, rng: &mut SplitMix64) {
+    let len = items.len();
+    for i in 0..n.min(len) {
+        let j = i + rng.next_below(len - i);
+        items.swap(i, j);
+    }
+}
+
+/// Selects a random subset of followers from a stored blob for manual
+/// verification.
+///
+/// Usernames are de-duplicated first, so the sample never contains the same
+/// account twice. The selection is fully determined by `seed`: calling this
+/// function again with the same blob, `n` and `seed` yields the same sample.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing a follower list serialized in
+///   MessagePack format.
+/// * `n`: The number of usernames to select. If it exceeds the number of unique
+///   followers, every follower is returned.
+/// * `seed`: The seed for the random generator (a `BigInt` in JS).
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript array of at most `n` usernames.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn sample_followers(mpack: &[u8], n: usize, seed: u64) -> Result<JsValue, JsValue> {
+    let followers = decode_followers(mpack, "followers")?;
+
+    let mut seen: HashSet<&str> = HashSet::with_capacity(followers.len());
+    let mut unique: Vec<&str> = followers
+        .iter()
+        .map(String::as_str)
+        .filter(|username| seen.insert(username))
+        .collect();
+
+    let mut rng = SplitMix64::new(seed);
+    partial_shuffle(&mut unique, n, &mut rng);
+    unique.truncate(n);
+
+    to_js(&unique)
+}
+
+/// Shuffles the order of a stored blob so it can be shared for debugging
+/// without revealing the order in which accounts followed.
+///
+/// Instagram lists followers newest first, so a blob's order leaks chronology.
+/// Every entry is kept exactly once; only the order changes, and it is fully
+/// determined by `seed`. Both username lists and `Follower` records are
+/// supported, and the blob keeps its layout.
+///
+/// # Arguments
+///
+/// * `mpack`: A byte slice (`&[u8]`) containing a follower blob serialized in
+///   MessagePack format.
+/// * `seed`: The seed for the random generator (a `BigInt` in JS).
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The shuffled blob, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization or serialization fails.
+#[wasm_bindgen]
+pub fn anonymize_order(mpack: &[u8], seed: u64) -> Result<Vec<u8>, JsValue> {
+    let mut rng = SplitMix64::new(seed);
+
+    if detect_format(mpack) == Some(BlobFormat::FollowerRecords) {
+        let mut records = decode_follower_records(mpack, "follower records")?;
+        let len = records.len();
+        partial_shuffle(&mut records, len, &mut rng);
+        return encode_mpack(&records);
+    }
+
+    let mut followers = decode_followers(mpack, "followers")?;
+    let len = followers.len();
+    partial_shuffle(&mut followers, len, &mut rng);
+    encode_mpack(&followers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_the_same_sequence() {
+        let mut a = SplitMix64::new(42);
+        let mut b = SplitMix64::new(42);
+        let mut c = SplitMix64::new(43);
+        let first: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        assert_eq!(first, (0..8).map(|_| b.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(first, (0..8).map(|_| c.next_u64()).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn partial_shuffle_samples_without_replacement() {
+        let mut items: Vec<u32> = (0..100).collect();
+        partial_shuffle(&mut items, 10, &mut SplitMix64::new(7));
+
+        let sample: HashSet<u32> = items[..10].iter().copied().collect();
+        assert_eq!(sample.len(), 10);
+        let mut all = items.clone();
+        all.sort_unstable();
+        assert_eq!(all, (0..100).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn anonymized_order_keeps_the_set_but_not_the_order() {
+        let followers: Vec<String> = (0..50).map(|i| format!("user{:02}", i)).collect();
+        let blob = rmp_serde::to_vec(&followers).unwrap();
+
+        let shuffled = anonymize_order(&blob, 7).unwrap();
+        assert_eq!(anonymize_order(&blob, 7).unwrap(), shuffled);
+        assert_ne!(anonymize_order(&blob, 8).unwrap(), shuffled);
+
+        let mut shuffled: Vec<String> = rmp_serde::from_slice(&shuffled).unwrap();
+        assert_ne!(shuffled, followers);
+        shuffled.sort();
+        assert_eq!(shuffled, followers);
+    }
+}
@@ -0,0 +1,153 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: self_test.rs
+* Description: A built-in end-to-end self test. Support requests often boil
+* down to "is the module working at all?"; `self_test` answers that with a
+* single call by running the core pipeline (JS conversion, serialize, decode,
+* diff, fingerprint) on a fixed list and checking every result against the known
+* answer.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::fingerprint::{fingerprint, FingerprintAlgo};
+use crate::{decode_followers, encode_mpack, followers_from_js, to_js, FollowerDiff};
+
+/// The outcome of `self_test`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SelfTestReport {
+    /// Whether every check passed.
+    pub ok: bool,
+    /// The names of the checks that passed, in the order they ran.
+    pub checks: Vec<&'static str>,
+    /// The name of the first failing check. Later checks are not run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed: Option<&'static str>,
+    /// What the failing check expected and what it got.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Runs a quick end-to-end check of the module on built-in data.
+///
+/// A known follower list is converted to a JavaScript array and back, serialized
+/// to MessagePack and decoded back, diffed against a modified copy, and
+/// fingerprinted in two different orders. Each step is compared with its known
+/// answer. The checks stop at the first failure, which is reported by name.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ ok, checks, failed?, detail? }`, where
+///   `checks` lists the names of the checks that passed.
+/// - `Err(JsValue)`: A JavaScript error object if the report cannot be converted to
+///   a JavaScript value.
+#[wasm_bindgen]
+pub fn self_test() -> Result<JsValue, JsValue> {
+    to_js(&run_checks(JS_CHECKS.iter().chain(&CORE_CHECKS)))
+}
+
+/// A single self-test check, run against the old and new built-in lists.
+type Check = fn(&[String], &[String]) -> Result<(), String>;
+
+/// The checks that cross the JS/WASM boundary. They need a JavaScript host, so
+/// they only run from `self_test`, before the core checks.
+const JS_CHECKS: [(&str, Check); 1] = [("js_round_trip", check_js_round_trip)];
+
+/// The checks of the pure Rust pipeline, in order.
+const CORE_CHECKS: [(&str, Check); 4] = [
+    ("mpack_round_trip", check_mpack_round_trip),
+    ("unfollower_diff", check_unfollower_diff),
+    ("new_follower_diff", check_new_follower_diff),
+    ("fingerprint_order_independent", check_fingerprint_order_independent),
+];
+
+/// Runs the given checks in order, stopping at the first failure.
+fn run_checks<'a>(checks: impl IntoIterator<Item = &'a (&'static str, Check)>) -> SelfTestReport {
+    let old_followers: Vec<String> = ["alice", "bob", "carol", "dave"].iter().map(|s| s.to_string()).collect();
+    let new_followers: Vec<String> = ["alice", "carol", "dave", "erin"].iter().map(|s| s.to_string()).collect();
+
+    let mut report = SelfTestReport { ok: true, checks: Vec::new(), failed: None, detail: None };
+    for &(name, check) in checks {
+        if let Err(detail) = check(&old_followers, &new_followers) {
+            report.ok = false;
+            report.failed = Some(name);
+            report.detail = Some(detail);
+            break;
+        }
+        report.checks.push(name);
+    }
+    report
+}
+
+fn check_js_round_trip(old_followers: &[String], _: &[String]) -> Result<(), String> {
+    let value = to_js(&old_followers).map_err(|_| "conversion to JavaScript failed".to_string())?;
+    let converted =
+        followers_from_js(value, "self-test list").map_err(|_| "conversion from JavaScript failed".to_string())?;
+    expect_eq(converted.as_slice(), old_followers)
+}
+
+fn check_mpack_round_trip(old_followers: &[String], _: &[String]) -> Result<(), String> {
+    let blob = encode_mpack(old_followers).map_err(|_| "serialization failed".to_string())?;
+    let decoded = decode_followers(&blob, "self-test blob").map_err(|_| "decoding failed".to_string())?;
+    expect_eq(decoded.as_slice(), old_followers)
+}
+
+fn check_unfollower_diff(old_followers: &[String], new_followers: &[String]) -> Result<(), String> {
+    let diff = diff_of(old_followers, new_followers);
+    expect_eq(diff.unfollowers.as_slice(), &["bob".to_string()])
+}
+
+fn check_new_follower_diff(old_followers: &[String], new_followers: &[String]) -> Result<(), String> {
+    let diff = diff_of(old_followers, new_followers);
+    expect_eq(diff.new_followers.as_slice(), &["erin".to_string()])
+}
+
+fn check_fingerprint_order_independent(old_followers: &[String], _: &[String]) -> Result<(), String> {
+    let forward = fingerprint(old_followers.iter().map(String::as_str), FingerprintAlgo::Fnv);
+    let reversed = fingerprint(old_followers.iter().rev().map(String::as_str), FingerprintAlgo::Fnv);
+    expect_eq(&reversed, &forward)
+}
+
+/// Diffs two lists the same way `diff_followers` does.
+fn diff_of(old_followers: &[String], new_followers: &[String]) -> FollowerDiff {
+    let old_set: HashSet<String> = old_followers.iter().cloned().collect();
+    let new_set: HashSet<String> = new_followers.iter().cloned().collect();
+    FollowerDiff::between(&old_set, &new_set, false)
+}
+
+fn expect_eq<T: PartialEq + std::fmt::Debug + ?Sized>(actual: &T, expected: &T) -> Result<(), String> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!("expected {:?}, got {:?}", expected, actual))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn core_checks_pass_on_a_healthy_build() {
+        let report = run_checks(&CORE_CHECKS);
+        assert_eq!(report.failed, None, "{:?}", report.detail);
+        assert!(report.ok);
+        assert_eq!(report.checks, CORE_CHECKS.map(|(name, _)| name));
+    }
+
+    #[test]
+    fn a_failing_check_stops_the_run() {
+        fn always_fails(_: &[String], _: &[String]) -> Result<(), String> {
+            Err("broken".to_string())
+        }
+        let checks: [(&str, Check); 2] = [CORE_CHECKS[0], ("always_fails", always_fails)];
+
+        let report = run_checks(checks.iter().chain(&CORE_CHECKS));
+        assert!(!report.ok);
+        assert_eq!(report.checks, ["mpack_round_trip"]);
+        assert_eq!(report.failed, Some("always_fails"));
+        assert_eq!(report.detail.as_deref(), Some("broken"));
+    }
+}
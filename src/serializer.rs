@@ -0,0 +1,56 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: serializer.rs
+* Description: A reusable MessagePack serializer for hot loops. Calling
+* `serialize_followers_to_mpack` repeatedly allocates a fresh output buffer
+* every time. `FollowerSerializer` instead owns a single buffer that is
+* cleared and reused across calls, so after the first few calls serializing
+* a list of similar size performs no buffer reallocation at all.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use js_sys::Uint8Array;
+
+use crate::followers_from_js;
+
+/// A follower serializer that reuses its internal output buffer.
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct FollowerSerializer {
+    buffer: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl FollowerSerializer {
+    /// Creates a serializer with an empty buffer.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> FollowerSerializer {
+        FollowerSerializer::default()
+    }
+
+    /// Serializes a list of follower usernames into the MessagePack binary format,
+    /// reusing the internal buffer.
+    ///
+    /// The returned `Uint8Array` is a copy of the buffer in JavaScript memory,
+    /// not a view into WASM memory, so it stays valid after the next call and
+    /// can be stored directly with `chrome.storage.local`.
+    ///
+    /// # Arguments
+    ///
+    /// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Uint8Array)`: The serialized data.
+    /// - `Err(JsValue)`: A JavaScript error object if the input is invalid or
+    ///   serialization fails.
+    pub fn serialize_into(&mut self, followers_js: JsValue) -> Result<Uint8Array, JsValue> {
+        let followers = followers_from_js(followers_js, "followers for serialization")?;
+
+        self.buffer.clear();
+        rmp_serde::encode::write(&mut self.buffer, &followers)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize to MessagePack: {}", e)))?;
+
+        Ok(Uint8Array::from(self.buffer.as_slice()))
+    }
+}
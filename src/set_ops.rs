@@ -0,0 +1,213 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: set_ops.rs
+* Description: Generic set-algebra helpers exposed to JavaScript. These are
+* thin wrappers over the same HashSet operations the diff functions rely on,
+* letting the extension compose its own analyses (mutuals, overlaps, custom
+* filters) without re-implementing set logic in JS. Every operation returns
+* a sorted, de-duplicated array so results are stable across calls.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::error::validate_usernames;
+use crate::{decode_followers, encode_mpack, followers_from_js, records_from_js, sorted, to_js};
+
+/// One managed account's follower list, as passed to `audience_overlap_matrix`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountFollowers {
+    pub account_id: String,
+    pub followers: Vec<String>,
+}
+
+/// Pairwise audience similarity between several accounts.
+///
+/// `matrix[i][j]` is the Jaccard similarity between the followers of
+/// `account_ids[i]` and `account_ids[j]`.
+#[derive(Debug, Serialize)]
+pub struct OverlapMatrix {
+    pub account_ids: Vec<String>,
+    pub matrix: Vec<Vec<f64>>,
+}
+
+/// Returns every username present in either list.
+///
+/// # Arguments
+///
+/// * `a_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+/// * `b_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings.
+/// - `Err(JsValue)`: A JavaScript error object if either input is invalid.
+#[wasm_bindgen]
+pub fn set_union(a_js: JsValue, b_js: JsValue) -> Result<JsValue, JsValue> {
+    let (a, b) = sets_from_js(a_js, b_js)?;
+    to_js(&sorted(a.union(&b).cloned().collect()))
+}
+
+/// Returns the usernames present in both lists.
+///
+/// # Arguments
+///
+/// * `a_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+/// * `b_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings.
+/// - `Err(JsValue)`: A JavaScript error object if either input is invalid.
+#[wasm_bindgen]
+pub fn set_intersection(a_js: JsValue, b_js: JsValue) -> Result<JsValue, JsValue> {
+    let (a, b) = sets_from_js(a_js, b_js)?;
+    to_js(&sorted(a.intersection(&b).cloned().collect()))
+}
+
+/// Returns the usernames present in `a_js` but not in `b_js`.
+///
+/// # Arguments
+///
+/// * `a_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+/// * `b_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A sorted JavaScript array of strings.
+/// - `Err(JsValue)`: A JavaScript error object if either input is invalid.
+#[wasm_bindgen]
+pub fn set_difference(a_js: JsValue, b_js: JsValue) -> Result<JsValue, JsValue> {
+    let (a, b) = sets_from_js(a_js, b_js)?;
+    to_js(&sorted(a.difference(&b).cloned().collect()))
+}
+
+/// Combines two stored follower blobs with a set operation, entirely in WASM.
+///
+/// Both operands stay binary: the result is returned as a new MessagePack blob
+/// instead of a JavaScript array, so no usernames cross the JS boundary.
+///
+/// # Arguments
+///
+/// * `a_mpack`: A byte slice (`&[u8]`) containing the first follower list.
+/// * `b_mpack`: A byte slice (`&[u8]`) containing the second follower list.
+/// * `op`: One of `"union"`, `"intersection"` or `"difference"` (`a` minus `b`).
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The sorted result list, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if either blob cannot be
+///   deserialized or the operation name is unknown.
+#[wasm_bindgen]
+pub fn mpack_set_op(a_mpack: &[u8], b_mpack: &[u8], op: &str) -> Result<Vec<u8>, JsValue> {
+    let a: HashSet<String> = decode_followers(a_mpack, "first set")?.into_iter().collect();
+    let b: HashSet<String> = decode_followers(b_mpack, "second set")?.into_iter().collect();
+
+    let result: Vec<String> = match op {
+        "union" => a.union(&b).cloned().collect(),
+        "intersection" => a.intersection(&b).cloned().collect(),
+        "difference" => a.difference(&b).cloned().collect(),
+        other => {
+            return Err(JsValue::from_str(&format!(
+                "Unknown set operation '{}': expected \"union\", \"intersection\" or \"difference\"",
+                other
+            )))
+        }
+    };
+
+    encode_mpack(&sorted(result))
+}
+
+/// Computes the pairwise Jaccard similarity of several accounts' audiences.
+///
+/// The Jaccard similarity of two follower sets is the size of their
+/// intersection divided by the size of their union: `1.0` means identical
+/// audiences and `0.0` means no shared followers. The diagonal is always `1.0`.
+///
+/// # Arguments
+///
+/// * `accounts_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `{ account_id, followers }` objects.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object mirroring `OverlapMatrix`, with rows and
+///   columns in input order.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid.
+#[wasm_bindgen]
+pub fn audience_overlap_matrix(accounts_js: JsValue) -> Result<JsValue, JsValue> {
+    let accounts: Vec<AccountFollowers> = records_from_js(accounts_js, "accounts")?;
+    accounts.iter().try_for_each(|account| validate_usernames(&account.followers))?;
+    let sets: Vec<HashSet<&str>> = accounts
+        .iter()
+        .map(|account| account.followers.iter().map(String::as_str).collect())
+        .collect();
+
+    let mut matrix = vec![vec![1.0; sets.len()]; sets.len()];
+    for i in 0..sets.len() {
+        for j in (i + 1)..sets.len() {
+            let similarity = jaccard(&sets[i], &sets[j]);
+            matrix[i][j] = similarity;
+            matrix[j][i] = similarity;
+        }
+    }
+
+    let account_ids = accounts.into_iter().map(|account| account.account_id).collect();
+    to_js(&OverlapMatrix { account_ids, matrix })
+}
+
+/// Computes the Jaccard similarity of two sets. Two empty sets are considered
+/// identical.
+pub(crate) fn jaccard<T: Eq + Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f64 {
+    let (smaller, larger) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let intersection = smaller.iter().filter(|item| larger.contains(*item)).count();
+    let union = a.len() + b.len() - intersection;
+    if union == 0 {
+        1.0
+    } else {
+        intersection as f64 / union as f64
+    }
+}
+
+/// Deserializes both operands into sets.
+fn sets_from_js(a_js: JsValue, b_js: JsValue) -> Result<(HashSet<String>, HashSet<String>), JsValue> {
+    let a = followers_from_js(a_js, "first set")?.into_iter().collect();
+    let b = followers_from_js(b_js, "second set")?.into_iter().collect();
+    Ok((a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set<'a>(items: &[&'a str]) -> HashSet<&'a str> {
+        items.iter().copied().collect()
+    }
+
+    #[test]
+    fn jaccard_is_intersection_over_union() {
+        assert_eq!(jaccard(&set(&["a", "b", "c", "d"]), &set(&["a", "b"])), 0.5);
+        assert_eq!(jaccard(&set(&["a", "b"]), &set(&["b", "c"])), 1.0 / 3.0);
+        assert_eq!(jaccard(&set(&["a"]), &set(&["b"])), 0.0);
+        assert_eq!(jaccard(&set(&[]), &set(&[])), 1.0);
+    }
+
+    fn blob(usernames: &[&str]) -> Vec<u8> {
+        rmp_serde::to_vec(usernames).unwrap()
+    }
+
+    #[test]
+    fn set_ops_on_blobs_return_sorted_blobs() {
+        let (a, b) = (blob(&["carol", "alice", "bob"]), blob(&["dave", "bob", "carol"]));
+        assert_eq!(mpack_set_op(&a, &b, "union").unwrap(), blob(&["alice", "bob", "carol", "dave"]));
+        assert_eq!(mpack_set_op(&a, &b, "intersection").unwrap(), blob(&["bob", "carol"]));
+        assert_eq!(mpack_set_op(&a, &b, "difference").unwrap(), blob(&["alice"]));
+        assert_eq!(mpack_set_op(&b, &a, "difference").unwrap(), blob(&["dave"]));
+    }
+}
@@ -0,0 +1,87 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: signing.rs
+* Description: Tamper-evident sharing of snapshot blobs. A signed blob is the
+* original MessagePack bytes followed by a 32-byte HMAC-SHA256 tag computed
+* with a secret key shared between sender and recipient. Verification checks
+* the tag in constant time and hands back the inner blob only if it matches,
+* so a recipient never processes data that was altered in transit.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use hmac::{Hmac, Mac};
+use js_sys::Uint8Array;
+use sha2::Sha256;
+
+use crate::error::TrackerError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The length of the HMAC-SHA256 tag appended to a signed blob.
+pub(crate) const TAG_LEN: usize = 32;
+
+/// Appends an HMAC-SHA256 tag to a blob.
+///
+/// # Arguments
+///
+/// * `mpack`: The blob to sign.
+/// * `secret_key_js`: The secret key, either a string (used as UTF-8 bytes) or a
+///   `Uint8Array`. It must not be empty.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The blob followed by its 32-byte tag.
+/// - `Err(JsValue)`: A JavaScript error object if the key is missing or invalid.
+#[wasm_bindgen]
+pub fn sign_mpack(mpack: &[u8], secret_key_js: JsValue) -> Result<Vec<u8>, JsValue> {
+    let mut mac = mac_for_key(secret_key_js)?;
+    mac.update(mpack);
+
+    let mut signed = Vec::with_capacity(mpack.len() + TAG_LEN);
+    signed.extend_from_slice(mpack);
+    signed.extend_from_slice(&mac.finalize().into_bytes());
+    Ok(signed)
+}
+
+/// Verifies a blob produced by `sign_mpack` and returns the inner blob.
+///
+/// # Arguments
+///
+/// * `signed`: The signed blob.
+/// * `secret_key_js`: The secret key, either a string (used as UTF-8 bytes) or a
+///   `Uint8Array`. It must not be empty.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The original blob, without its tag.
+/// - `Err(JsValue)`: `TrackerError::BadSignature` if the tag does not match (or the
+///   blob is too short to hold one), or an error if the key is missing or invalid.
+#[wasm_bindgen]
+pub fn verify_signed_mpack(signed: &[u8], secret_key_js: JsValue) -> Result<Vec<u8>, JsValue> {
+    let mut mac = mac_for_key(secret_key_js)?;
+    if signed.len() < TAG_LEN {
+        return Err(TrackerError::BadSignature.into());
+    }
+
+    let (mpack, tag) = signed.split_at(signed.len() - TAG_LEN);
+    mac.update(mpack);
+    mac.verify_slice(tag).map_err(|_| TrackerError::BadSignature)?;
+    Ok(mpack.to_vec())
+}
+
+/// Builds an HMAC instance from a string or `Uint8Array` key.
+fn mac_for_key(secret_key_js: JsValue) -> Result<HmacSha256, JsValue> {
+    let key: Vec<u8> = if let Some(key) = secret_key_js.as_string() {
+        key.into_bytes()
+    } else if let Some(key) = secret_key_js.dyn_ref::<Uint8Array>() {
+        key.to_vec()
+    } else {
+        return Err(JsValue::from_str("Secret key must be a string or a Uint8Array"));
+    };
+
+    if key.is_empty() {
+        return Err(JsValue::from_str("Secret key must not be empty"));
+    }
+    HmacSha256::new_from_slice(&key).map_err(|e| JsValue::from_str(&format!("Invalid secret key: {}", e)))
+}
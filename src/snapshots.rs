@@ -0,0 +1,171 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: snapshots.rs
+* Description: Timestamped snapshot series. Besides the latest follower list,
+* the extension can keep every scrape as a `Snapshot` in a MessagePack array.
+* The series grows with each check, so it can be compacted by dropping
+* snapshots that add nothing over the one kept before them.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::set_ops::jaccard;
+use crate::{decode_records, encode_mpack, to_js};
+
+/// The `compact_history` threshold used when none is given: only exact
+/// duplicates are dropped.
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 1.0;
+
+/// A follower list captured at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// When the scrape ran, in milliseconds since the Unix epoch.
+    pub timestamp: f64,
+    /// The usernames found by the scrape.
+    pub followers: Vec<String>,
+}
+
+/// Shrinks a snapshot series by dropping snapshots that are (near-)duplicates
+/// of the snapshot kept before them.
+///
+/// Snapshots are ordered by timestamp and each one is compared, as a set, to
+/// the last snapshot that was kept. It is dropped when their Jaccard similarity
+/// is at least `similarity_threshold`. Comparing against the last kept snapshot
+/// rather than the immediate predecessor means that slow drift is never
+/// compacted away, however small each step. The first snapshot is always kept.
+///
+/// # Arguments
+///
+/// * `snapshots_mpack`: A byte slice (`&[u8]`) containing the snapshot series,
+///   serialized in MessagePack format.
+/// * `similarity_threshold`: An optional similarity, from `0.0` to `1.0`, at or above
+///   which a snapshot is considered a duplicate. Defaults to `1.0`, which drops
+///   only exact duplicates; a lower value such as `0.98` also absorbs snapshots
+///   that differ by a flaky entry or two.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The compacted series, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if the threshold is outside `0.0..=1.0`,
+///   or if deserialization or serialization fails.
+#[wasm_bindgen]
+pub fn compact_history(snapshots_mpack: &[u8], similarity_threshold: Option<f64>) -> Result<Vec<u8>, JsValue> {
+    let similarity_threshold = similarity_threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    if !(0.0..=1.0).contains(&similarity_threshold) {
+        return Err(JsValue::from_str(&format!(
+            "Invalid similarity threshold {}: expected a number from 0 to 1",
+            similarity_threshold
+        )));
+    }
+    let snapshots = decode_snapshots(snapshots_mpack)?;
+
+    let mut compacted: Vec<Snapshot> = Vec::with_capacity(snapshots.len());
+    let mut last_kept: Option<HashSet<String>> = None;
+    for snapshot in snapshots {
+        let set: HashSet<String> = snapshot.followers.iter().cloned().collect();
+        if let Some(previous) = &last_kept {
+            if jaccard(previous, &set) >= similarity_threshold {
+                continue;
+            }
+        }
+        last_kept = Some(set);
+        compacted.push(snapshot);
+    }
+
+    encode_mpack(&compacted)
+}
+
+/// Aggregate follower movement over a window of snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowStats {
+    /// The number of snapshots in the window.
+    pub snapshots: usize,
+    /// Followers gained across the consecutive checks in the window.
+    pub gained: usize,
+    /// Followers lost across the consecutive checks in the window.
+    pub lost: usize,
+    /// `gained - lost`, which equals the change in unique followers from the
+    /// first to the last snapshot of the window.
+    pub net_change: i64,
+}
+
+/// Summarizes follower movement over just the last `k` snapshots, for
+/// dashboard stats such as "last 7 checks".
+///
+/// Each pair of consecutive snapshots in the window is diffed and the gains and
+/// losses are summed, so an account that leaves and returns within the window
+/// counts once in each total. A window of `k` snapshots spans `k - 1` checks.
+///
+/// # Arguments
+///
+/// * `snapshots_mpack`: A byte slice (`&[u8]`) containing the snapshot series,
+///   serialized in MessagePack format.
+/// * `k`: How many of the most recent snapshots to include. A series shorter
+///   than `k` is used whole.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ snapshots, gained, lost, net_change }`.
+/// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+#[wasm_bindgen]
+pub fn windowed_stats(snapshots_mpack: &[u8], k: usize) -> Result<JsValue, JsValue> {
+    let snapshots = decode_snapshots(snapshots_mpack)?;
+    let window = &snapshots[snapshots.len().saturating_sub(k)..];
+
+    let sets: Vec<HashSet<&String>> = window.iter().map(|snapshot| snapshot.followers.iter().collect()).collect();
+    let (mut gained, mut lost) = (0, 0);
+    for pair in sets.windows(2) {
+        gained += pair[1].difference(&pair[0]).count();
+        lost += pair[0].difference(&pair[1]).count();
+    }
+
+    to_js(&WindowStats { snapshots: window.len(), gained, lost, net_change: gained as i64 - lost as i64 })
+}
+
+/// Deserializes a snapshot series ordered by timestamp, treating an empty slice
+/// as an empty series.
+pub(crate) fn decode_snapshots(snapshots_mpack: &[u8]) -> Result<Vec<Snapshot>, JsValue> {
+    let mut snapshots: Vec<Snapshot> = decode_records(snapshots_mpack, "snapshots")?;
+    snapshots.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(timestamp: f64, followers: impl IntoIterator<Item = String>) -> Snapshot {
+        Snapshot { timestamp, followers: followers.into_iter().collect() }
+    }
+
+    fn series(snapshots: &[Snapshot]) -> Vec<u8> {
+        rmp_serde::to_vec(snapshots).unwrap()
+    }
+
+    fn compacted(snapshots: &[Snapshot], threshold: Option<f64>) -> Vec<Snapshot> {
+        rmp_serde::from_slice(&compact_history(&series(snapshots), threshold).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn collapses_99_percent_similar_snapshots_at_0_98() {
+        // 100 shared followers; the second scrape swaps one for another, for a
+        // Jaccard similarity of 99/101 (about 0.980).
+        let base: Vec<String> = (0..100).map(|i| format!("user{}", i)).collect();
+        let swapped = base[1..].iter().cloned().chain(["newcomer".to_string()]);
+        let snapshots = [snapshot(1.0, base.clone()), snapshot(2.0, swapped)];
+
+        assert_eq!(compacted(&snapshots, Some(0.98)), [snapshots[0].clone()]);
+        assert_eq!(compacted(&snapshots, None), snapshots);
+    }
+
+    #[test]
+    fn drops_exact_duplicates_by_default() {
+        let followers = || ["alice".to_string(), "bob".to_string()];
+        let snapshots = [snapshot(1.0, followers()), snapshot(2.0, followers()), snapshot(3.0, ["alice".to_string()])];
+
+        assert_eq!(compacted(&snapshots, None), [snapshots[0].clone(), snapshots[2].clone()]);
+    }
+}
@@ -0,0 +1,51 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: state.rs
+* Description: The module's only global state, kept in one place so it can be
+* reset as a whole. Today that is an optional logger callback that receives
+* diagnostic messages (for example, when an incomplete snapshot skips
+* unfollower detection). The state is thread-local and lives as long as the
+* WASM instance: it is empty on instantiation, set by `set_logger`, and
+* cleared by `reset_state`. Extensions should call `reset_state` on a context
+* change, such as a reload, so a stale callback never fires into a torn-down
+* page. Any global added later must be cleared by `reset_state` too.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use std::cell::RefCell;
+
+use js_sys::Function;
+use wasm_bindgen::prelude::*;
+
+thread_local! {
+    static LOGGER: RefCell<Option<Function>> = const { RefCell::new(None) };
+}
+
+/// Installs a callback that receives the module's diagnostic messages.
+///
+/// The callback is called with a single string argument and replaces any
+/// logger set before. Errors it throws are ignored.
+///
+/// # Arguments
+///
+/// * `callback`: A JavaScript function taking one string.
+#[wasm_bindgen]
+pub fn set_logger(callback: Function) {
+    LOGGER.with(|logger| *logger.borrow_mut() = Some(callback));
+}
+
+/// Clears all module-level state back to its defaults.
+///
+/// After this call no logger is installed, so logging is a no-op until
+/// `set_logger` is called again. Calling it when nothing was set is harmless.
+#[wasm_bindgen]
+pub fn reset_state() {
+    LOGGER.with(|logger| *logger.borrow_mut() = None);
+}
+
+/// Sends `message` to the installed logger, if any.
+pub(crate) fn log(message: &str) {
+    LOGGER.with(|logger| {
+        if let Some(callback) = logger.borrow().as_ref() {
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(message));
+        }
+    });
+}
@@ -0,0 +1,105 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: storage.rs
+* Description: Storage-usage diagnostics. These functions do not change how
+* anything is stored; they measure what a follower list costs in the current
+* format and what an alternative would cost, so the user (or a future
+* version) can decide whether the alternative is worth it for their list.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+
+use crate::error::validate_username;
+use crate::follower::Follower;
+use crate::{encode_mpack, followers_from_js, records_from_js, to_js};
+
+/// The DEFLATE level used for estimates; 6 is zlib's default trade-off.
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// How much a follower blob would shrink if compressed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompressionStats {
+    /// The size of the plain MessagePack blob.
+    pub raw_mpack_bytes: usize,
+    /// The size of the same blob after DEFLATE compression.
+    pub compressed_bytes: usize,
+    /// `compressed_bytes / raw_mpack_bytes`; below `1.0` means compression helps.
+    pub ratio: f64,
+}
+
+/// Measures how well a follower list's MessagePack blob compresses.
+///
+/// The list is serialized exactly as `serialize_followers_to_mpack` would store
+/// it, and the blob is then compressed with raw DEFLATE (level 6). Usernames
+/// share a lot of structure, so large lists typically compress well, while
+/// tiny lists may not compress at all.
+///
+/// # Arguments
+///
+/// * `followers_js`: A `JsValue` from JavaScript, expected to be an array of strings.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ raw_mpack_bytes, compressed_bytes, ratio }`.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid or
+///   serialization fails.
+#[wasm_bindgen]
+pub fn compression_stats(followers_js: JsValue) -> Result<JsValue, JsValue> {
+    let followers = followers_from_js(followers_js, "followers")?;
+    let raw = encode_mpack(&followers)?;
+    let compressed = miniz_oxide::deflate::compress_to_vec(&raw, COMPRESSION_LEVEL);
+
+    to_js(&CompressionStats {
+        raw_mpack_bytes: raw.len(),
+        compressed_bytes: compressed.len(),
+        ratio: compressed.len() as f64 / raw.len() as f64,
+    })
+}
+
+/// The cost of storing the same accounts by username and by `user_id`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IdStorageSavings {
+    /// The size of the accounts stored as a MessagePack list of usernames.
+    pub username_bytes: usize,
+    /// The size of the accounts stored as a MessagePack list of `user_id`s.
+    pub id_bytes: usize,
+    /// How much smaller the id list is, as a percentage of `username_bytes`.
+    /// Negative if the id list is larger.
+    pub savings_pct: f64,
+}
+
+/// Measures how much storage switching from usernames to `user_id`s would save.
+///
+/// The same accounts are serialized both ways: as the username list written by
+/// `serialize_followers_to_mpack`, and as a list of numeric ids, each encoded
+/// in the smallest MessagePack integer type that fits (at most 9 bytes). Long
+/// usernames therefore benefit the most.
+///
+/// # Arguments
+///
+/// * `followers_with_ids_js`: A `JsValue` from JavaScript, expected to be an array of
+///   `Follower` objects with at least `username` and `user_id`.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(JsValue)`: A JavaScript object `{ username_bytes, id_bytes, savings_pct }`.
+/// - `Err(JsValue)`: A JavaScript error object if the input is invalid or
+///   serialization fails.
+#[wasm_bindgen]
+pub fn id_storage_savings(followers_with_ids_js: JsValue) -> Result<JsValue, JsValue> {
+    let followers: Vec<Follower> = records_from_js(followers_with_ids_js, "follower records")?;
+    followers.iter().try_for_each(|follower| validate_username(&follower.username))?;
+
+    let usernames: Vec<&str> = followers.iter().map(|follower| follower.username.as_str()).collect();
+    let ids: Vec<u64> = followers.iter().map(|follower| follower.user_id).collect();
+    let username_bytes = encode_mpack(&usernames)?.len();
+    let id_bytes = encode_mpack(&ids)?.len();
+
+    to_js(&IdStorageSavings {
+        username_bytes,
+        id_bytes,
+        savings_pct: (1.0 - id_bytes as f64 / username_bytes as f64) * 100.0,
+    })
+}
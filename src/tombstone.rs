@@ -0,0 +1,94 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tombstone.rs
+* Description: Soft-delete snapshots. Instead of dropping an account as soon
+* as it disappears from a scrape, the account is kept as a tombstone stamped
+* with the time it went missing. If it reappears within the grace period it
+* is quietly restored; only once the grace period expires is it purged. This
+* smooths over accounts that briefly unfollow and refollow, and over flaky
+* scrapes that miss a few entries.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::error::{validate_duration, validate_timestamp};
+use crate::{decode_records, encode_mpack, followers_from_js};
+
+/// A follower entry in a tombstone-aware snapshot.
+///
+/// `removed_at` is `None` while the account is an active follower and holds
+/// the timestamp (milliseconds since the Unix epoch) at which it was first
+/// found missing once it becomes a tombstone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackedFollower {
+    pub username: String,
+    pub removed_at: Option<f64>,
+}
+
+/// Updates a tombstone-aware snapshot with the latest follower list.
+///
+/// Each account in the previous snapshot is handled as follows:
+/// - Present in the new list: kept as an active follower, clearing any tombstone.
+/// - Missing and currently active: turned into a tombstone stamped with `now`.
+/// - Missing and already a tombstone: kept until it is older than `grace_ms`,
+///   then purged from the snapshot.
+///
+/// Accounts in the new list that were not in the previous snapshot are
+/// appended as active followers.
+///
+/// # Arguments
+///
+/// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of strings
+///   representing the latest list of followers.
+/// * `old_snapshot_mpack`: A byte slice (`&[u8]`) containing the previous snapshot as
+///   returned by this function. An empty slice starts a new snapshot.
+/// * `now`: The current time in milliseconds since the Unix epoch (e.g. `Date.now()`).
+/// * `grace_ms`: How long, in milliseconds, a tombstone is retained before being purged.
+///
+/// # Returns
+///
+/// A `Result` containing either:
+/// - `Ok(Vec<u8>)`: The updated snapshot, serialized in MessagePack format.
+/// - `Err(JsValue)`: A JavaScript error object if `now` is not a valid timestamp or
+///   `grace_ms` is not a valid duration, or if deserialization or processing fails.
+#[wasm_bindgen]
+pub fn update_with_tombstones(
+    new_followers_js: JsValue,
+    old_snapshot_mpack: &[u8],
+    now: f64,
+    grace_ms: f64,
+) -> Result<Vec<u8>, JsValue> {
+    validate_timestamp("now", now)?;
+    validate_duration("grace_ms", grace_ms)?;
+    let old_snapshot: Vec<TrackedFollower> = decode_records(old_snapshot_mpack, "old snapshot")?;
+    let new_followers = followers_from_js(new_followers_js, "new followers")?;
+    let new_set: HashSet<&str> = new_followers.iter().map(String::as_str).collect();
+
+    let mut seen: HashSet<String> = HashSet::with_capacity(old_snapshot.len() + new_followers.len());
+    let mut updated: Vec<TrackedFollower> = Vec::with_capacity(old_snapshot.len() + new_followers.len());
+
+    for entry in old_snapshot {
+        if !seen.insert(entry.username.clone()) {
+            continue;
+        }
+        let removed_at = if new_set.contains(entry.username.as_str()) {
+            None
+        } else {
+            match entry.removed_at {
+                None => Some(now),
+                Some(removed_at) if now - removed_at > grace_ms => continue,
+                Some(removed_at) => Some(removed_at),
+            }
+        };
+        updated.push(TrackedFollower { username: entry.username, removed_at });
+    }
+
+    for username in new_followers {
+        if seen.insert(username.clone()) {
+            updated.push(TrackedFollower { username, removed_at: None });
+        }
+    }
+
+    encode_mpack(&updated)
+}
@@ -0,0 +1,73 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tracker.rs
+* Description: A stateful tracker for long-lived sessions. The stateless diff
+* functions deserialize the stored blob and rebuild its HashSet on every
+* call. When the extension checks every few minutes, `Tracker` keeps the
+* current follower set in WASM memory instead, so each check only pays for
+* deserializing the fresh scrape.
+* SPDX-License-Identifier: Apache-2.0 */
+
+use wasm_bindgen::prelude::*;
+use std::collections::HashSet;
+
+use crate::{decode_followers, encode_mpack, followers_from_js, sorted, to_js, FollowerDiff};
+
+/// Holds the current follower set between successive checks.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Tracker {
+    current: HashSet<String>,
+}
+
+#[wasm_bindgen]
+impl Tracker {
+    /// Loads a tracker from a stored follower blob. An empty slice creates a
+    /// tracker with no followers, as on a first run.
+    ///
+    /// # Arguments
+    ///
+    /// * `mpack`: A byte slice (`&[u8]`) containing a follower list serialized in
+    ///   MessagePack format.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Tracker)`: The loaded tracker.
+    /// - `Err(JsValue)`: A JavaScript error object if deserialization fails.
+    pub fn from_mpack(mpack: &[u8]) -> Result<Tracker, JsValue> {
+        let current = decode_followers(mpack, "followers")?.into_iter().collect();
+        Ok(Tracker { current })
+    }
+
+    /// Diffs the latest follower list against the held set, then adopts the new
+    /// list as the current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_followers_js`: A `JsValue` from JavaScript, expected to be an array of
+    ///   strings representing the latest list of followers.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(JsValue)`: A JavaScript object mirroring `FollowerDiff`.
+    /// - `Err(JsValue)`: A JavaScript error object if the input is invalid. The held
+    ///   state is left unchanged in that case.
+    pub fn update(&mut self, new_followers_js: JsValue) -> Result<JsValue, JsValue> {
+        let new_set: HashSet<String> = followers_from_js(new_followers_js, "new followers")?.into_iter().collect();
+        let diff = FollowerDiff::between(&self.current, &new_set, false);
+        self.current = new_set;
+        to_js(&diff)
+    }
+
+    /// Serializes the current follower set into MessagePack, sorted by username.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Vec<u8>)`: A byte vector (`Uint8Array` in JS) of the serialized data.
+    /// - `Err(JsValue)`: A JavaScript error object if serialization fails.
+    pub fn to_mpack(&self) -> Result<Vec<u8>, JsValue> {
+        encode_mpack(&sorted(self.current.iter().cloned().collect()))
+    }
+}
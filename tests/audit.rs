@@ -0,0 +1,28 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/audit.rs
+* Description: WASM tests for the encoding audit in audit.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{json, mpack};
+use serde_json::json;
+use unfollower_logic::audit::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn flags_a_username_containing_a_replacement_character() {
+    let blob = mpack(&["alice", "jo\u{FFFD}o", "bob", "carol\u{0}"]);
+
+    let anomalies = json(audit_encoding(&blob).unwrap());
+    assert_eq!(
+        anomalies,
+        json!([
+            { "username": "jo\u{FFFD}o", "issue": "replacement_character" },
+            { "username": "carol\u{0}", "issue": "control_character" },
+        ])
+    );
+    assert_eq!(json(audit_encoding(&mpack(&["alice", "josé"])).unwrap()), json!([]));
+}
@@ -0,0 +1,57 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/cancellation.rs
+* Description: WASM tests for `find_unfollowers_cancellable` in lib.rs, driven
+* by a JavaScript callback that counts how often it is polled.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, json, mpack};
+use js_sys::Function;
+use serde_json::json;
+use std::cell::Cell;
+use std::rc::Rc;
+use unfollower_logic::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+/// A callback that asks to cancel from its `call`-th call onwards, together
+/// with a counter of how often it was called.
+fn cancel_on(call: u32) -> (Closure<dyn FnMut() -> bool>, Rc<Cell<u32>>) {
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    let callback = Closure::new(move || {
+        counter.set(counter.get() + 1);
+        counter.get() >= call
+    });
+    (callback, calls)
+}
+
+fn names(count: usize) -> Vec<String> {
+    (0..count).map(|i| format!("user{}", i)).collect()
+}
+
+#[wasm_bindgen_test]
+fn cancelling_mid_operation_returns_cancelled() {
+    let (callback, calls) = cancel_on(3);
+    let should_cancel: &Function = callback.as_ref().unchecked_ref();
+
+    // The first call is before any work and the second before the first
+    // username, so the third lands part-way through the new list.
+    let result = find_unfollowers_cancellable(js(&names(10_000)), &mpack(&names(10_000)), should_cancel);
+    assert_eq!(error_message(result), "Operation cancelled");
+    assert_eq!(calls.get(), 3);
+}
+
+#[wasm_bindgen_test]
+fn a_callback_that_never_cancels_gets_the_full_result() {
+    let (callback, calls) = cancel_on(u32::MAX);
+    let should_cancel: &Function = callback.as_ref().unchecked_ref();
+
+    let old = mpack(&["alice", "bob", "carol"]);
+    let unfollowers = find_unfollowers_cancellable(js(&["bob"]), &old, should_cancel).unwrap();
+    assert_eq!(json(unfollowers), json!(["alice", "carol"]));
+    assert!(calls.get() >= 1);
+}
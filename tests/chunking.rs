@@ -0,0 +1,41 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/chunking.rs
+* Description: WASM tests for the chunked storage helpers in chunking.rs,
+* which return and take JavaScript arrays of `Uint8Array`s.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, json};
+use js_sys::{Array, Uint8Array};
+use unfollower_logic::chunking::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn chunks_round_trip_with_a_small_max_bytes() {
+    let followers = ["alice", "bob", "zoë", "björk", "日本語", "carol", "dave", "ǆemal"];
+    let max_bytes = 16;
+
+    let chunks = chunk_mpack(js(&followers), max_bytes).unwrap();
+    let array: Array = chunks.clone().dyn_into().unwrap();
+    assert!(array.length() > 1);
+    for chunk in array.iter() {
+        let bytes = chunk.dyn_into::<Uint8Array>().unwrap().to_vec();
+        assert!(bytes.len() <= max_bytes, "chunk of {} bytes", bytes.len());
+        // Every chunk is a complete username list on its own.
+        let _: Vec<String> = rmp_serde::from_slice(&bytes).unwrap();
+    }
+
+    assert_eq!(json(dechunk_mpack(chunks).unwrap()), serde_json::json!(followers));
+}
+
+#[wasm_bindgen_test]
+fn rejects_a_zero_chunk_size_and_an_unsplittable_username() {
+    assert_eq!(error_message(chunk_mpack(js(&["alice"]), 0)), "Chunk size must be at least 1 byte");
+
+    let message = error_message(chunk_mpack(js(&["alice"]), 4));
+    assert_eq!(message, "Username 'alice' needs 7 bytes and cannot fit in a 4-byte chunk");
+}
@@ -0,0 +1,38 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/classify.rs
+* Description: WASM tests for the username heuristics in classify.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, json};
+use serde_json::json;
+use unfollower_logic::classify::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn classifies_with_the_default_threshold() {
+    let unfollowers = js(&["user12839402", "jane.doe", "ab12", "mike_1990", "jane.doe"]);
+
+    let classified = json(classify_unfollowers(unfollowers, None).unwrap());
+    assert_eq!(
+        classified,
+        json!({ "likely_bots": ["ab12", "user12839402"], "likely_real": ["jane.doe", "mike_1990"] })
+    );
+}
+
+#[wasm_bindgen_test]
+fn a_stricter_threshold_only_keeps_the_digit_run() {
+    let classified = json(classify_unfollowers(js(&["user12839402", "ab12"]), Some(0.9)).unwrap());
+    assert_eq!(classified, json!({ "likely_bots": ["user12839402"], "likely_real": ["ab12"] }));
+}
+
+#[wasm_bindgen_test]
+fn rejects_a_threshold_outside_zero_to_one() {
+    for threshold in [1.5, -0.1, f64::NAN] {
+        let message = error_message(classify_unfollowers(js(&["alice"]), Some(threshold)));
+        assert!(message.starts_with("Invalid digit ratio threshold"), "{}", message);
+    }
+}
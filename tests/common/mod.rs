@@ -0,0 +1,43 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/common/mod.rs
+* Description: Helpers shared by the WASM test suites. They build the
+* JavaScript values and MessagePack blobs the exported functions take, and
+* read their results back as JSON for easy assertions.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![allow(dead_code)]
+
+use serde::Serialize;
+use std::fmt::Debug;
+use wasm_bindgen::JsValue;
+
+/// Converts a Rust value into the JavaScript value the extension would pass,
+/// with maps as plain objects.
+pub fn js<T: Serialize + ?Sized>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true))
+        .unwrap()
+}
+
+/// Reads a JavaScript result back as JSON.
+pub fn json(value: JsValue) -> serde_json::Value {
+    serde_wasm_bindgen::from_value(value).unwrap()
+}
+
+/// Serializes a value to MessagePack the same way the crate does.
+pub fn mpack<T: Serialize + ?Sized>(value: &T) -> Vec<u8> {
+    rmp_serde::to_vec(value).unwrap()
+}
+
+/// Returns the message of the error a call failed with.
+pub fn error_message<T: Debug>(result: Result<T, JsValue>) -> String {
+    result.unwrap_err().as_string().expect("errors are reported as strings")
+}
+
+/// Reads a JavaScript array of usernames back in sorted order, for functions
+/// whose results come out in no particular order.
+pub fn sorted_names(value: JsValue) -> Vec<String> {
+    let mut names: Vec<String> = serde_wasm_bindgen::from_value(value).unwrap();
+    names.sort_unstable();
+    names
+}
@@ -0,0 +1,52 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/delta.rs
+* Description: WASM tests for the delta encoding in delta.rs, whose results
+* are JavaScript objects holding `Uint8Array` blobs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{js, json, mpack};
+use js_sys::{Reflect, Uint8Array};
+use serde_json::json;
+use unfollower_logic::delta::*;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+/// Reads the `Uint8Array` property `key` of a result object.
+fn blob(result: &JsValue, key: &str) -> Vec<u8> {
+    Uint8Array::new(&Reflect::get(result, &key.into()).unwrap()).to_vec()
+}
+
+fn decode(blob: &[u8]) -> Vec<String> {
+    rmp_serde::from_slice(blob).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn delta_blobs_reconstruct_the_new_snapshot() {
+    let old = mpack(&["alice", "bob", "carol", "dave"]);
+    let new = ["carol", "alice", "erin", "frank"];
+
+    let delta = diff_as_delta_blobs(js(&new), &old).unwrap();
+    let (added, removed) = (blob(&delta, "added_mpack"), blob(&delta, "removed_mpack"));
+    assert_eq!(decode(&added), ["erin", "frank"]);
+    assert_eq!(decode(&removed), ["bob", "dave"]);
+
+    let rebuilt = apply_snapshot_delta(&old, &added, &removed).unwrap();
+    assert_eq!(decode(&rebuilt), ["alice", "carol", "erin", "frank"]);
+}
+
+#[wasm_bindgen_test]
+fn three_scrapes_are_applied_in_order() {
+    let old = mpack(&["alice", "bob"]);
+    // carol arrives, bob leaves, then carol leaves again and dave arrives.
+    let scrapes = js(&[vec!["alice", "bob", "carol"], vec!["alice", "carol"], vec!["alice", "dave"]]);
+
+    let result = process_scrape_series(scrapes, &old).unwrap();
+    assert_eq!(decode(&blob(&result, "final_snapshot_mpack")), ["alice", "dave"]);
+    let total = |key: &str| json(Reflect::get(&result, &key.into()).unwrap());
+    assert_eq!(total("total_gained"), json!(["carol", "dave"]));
+    assert_eq!(total("total_lost"), json!(["bob", "carol"]));
+}
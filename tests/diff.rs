@@ -0,0 +1,289 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/diff.rs
+* Description: WASM tests for the follower diff functions in lib.rs. They take
+* their follower lists as JavaScript values, so they run under Node.js with
+* `wasm-pack test --node`.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, json, mpack, sorted_names};
+use serde_json::json;
+use unfollower_logic::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn diff_with_stats_counts_duplicates_collapsed_on_both_sides() {
+    let old = mpack(&["alice", "bob", "bob", "carol"]);
+    let new = js(&["alice", "alice", "alice", "carol", "dave"]);
+
+    let stats = json(diff_with_stats(new, &old).unwrap());
+    assert_eq!(
+        stats,
+        json!({
+            "unfollowers": ["bob"],
+            "new_followers": ["dave"],
+            "old_count": 3,
+            "new_count": 3,
+            "old_duplicates_removed": 1,
+            "new_duplicates_removed": 2,
+        })
+    );
+}
+
+#[wasm_bindgen_test]
+fn unfollowers_i_didnt_follow_excludes_accounts_i_follow() {
+    let old = mpack(&["alice", "friend", "stranger"]);
+    let new = js(&["alice"]);
+    let following = js(&["alice", "friend"]);
+
+    let unfollowers = json(find_unfollowers_i_didnt_follow(new, &old, following).unwrap());
+    assert_eq!(unfollowers, json!(["stranger"]));
+}
+
+#[wasm_bindgen_test]
+fn reconcile_reports_a_missing_and_a_spurious_entry() {
+    let expected = mpack(&["alice", "bob", "carol"]);
+    let actual = mpack(&["carol", "alice", "mallory", "alice"]);
+
+    let reconciliation = json(reconcile(&expected, &actual).unwrap());
+    assert_eq!(
+        reconciliation,
+        json!({ "missing_in_actual": ["bob"], "extra_in_actual": ["mallory"], "matches": false })
+    );
+    assert_eq!(json(reconcile(&expected, &expected).unwrap())["matches"], json!(true));
+}
+
+#[wasm_bindgen_test]
+fn complete_partial_snapshot_is_diffed_like_a_plain_list() {
+    let old = mpack(&["alice", "bob"]);
+    let new = js(&json!({ "complete": true, "followers": ["alice", "carol"] }));
+
+    assert_eq!(json(find_unfollowers(new.clone(), &old, None).unwrap()), json!(["bob"]));
+    assert_eq!(json(find_new_followers(new, &old).unwrap()), json!(["carol"]));
+}
+
+#[wasm_bindgen_test]
+fn incomplete_snapshot_only_reports_new_followers() {
+    let old = mpack(&["alice", "bob"]);
+    let new = js(&json!({ "complete": false, "followers": ["carol"] }));
+
+    assert_eq!(json(find_unfollowers(new.clone(), &old, None).unwrap()), json!([]));
+    assert_eq!(json(find_new_followers(new, &old).unwrap()), json!(["carol"]));
+}
+
+#[wasm_bindgen_test]
+fn joined_unfollowers_use_the_separator() {
+    let old = mpack(&["carol", "alice", "bob", "dave"]);
+    let new = js(&["bob"]);
+
+    assert_eq!(find_unfollowers_joined(new, &old, "\n").unwrap(), "alice\ncarol\ndave");
+    assert_eq!(find_unfollowers_joined(js(&["alice"]), &mpack(&["alice"]), "\n").unwrap(), "");
+}
+
+#[wasm_bindgen_test]
+fn joined_unfollowers_reject_a_username_containing_the_separator() {
+    let old = mpack(&["a,b", "c"]);
+    let message = error_message(find_unfollowers_joined(js(&["c"]), &old, ","));
+    assert_eq!(message, "Username 'a,b' contains the separator \",\"");
+    assert_eq!(error_message(find_unfollowers_joined(js(&["c"]), &old, "")), "Separator must not be empty");
+}
+
+#[wasm_bindgen_test]
+fn ordered_serialization_keeps_first_occurrence_order() {
+    let blob = serialize_followers_ordered(js(&["zoe", "alice", "zoe", "mike", "alice"])).unwrap();
+    let decoded: Vec<String> = rmp_serde::from_slice(&blob).unwrap();
+    assert_eq!(decoded, ["zoe", "alice", "mike"]);
+}
+
+#[wasm_bindgen_test]
+fn rejects_a_10000_character_username_from_javascript() {
+    let new = js(&["alice".to_string(), "x".repeat(10_000)]);
+    let message = error_message(find_unfollowers(new, &mpack(&["alice"]), None));
+    assert!(message.starts_with("Username is too long (10000 characters, maximum is 100)"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn diff_followers_includes_retained_only_when_asked() {
+    let old = mpack(&["alice", "bob"]);
+
+    let with = json(diff_followers(js(&["alice", "carol"]), &old, true).unwrap());
+    assert_eq!(with, json!({ "unfollowers": ["bob"], "new_followers": ["carol"], "retained": ["alice"] }));
+
+    let without = json(diff_followers(js(&["alice", "carol"]), &old, false).unwrap());
+    assert_eq!(without, json!({ "unfollowers": ["bob"], "new_followers": ["carol"] }));
+}
+
+#[wasm_bindgen_test]
+fn alpha_distribution_buckets_by_lowercased_first_letter() {
+    let followers = mpack(&["Alice", "adam", "bob", "_under", "9lives", "Émile", "bob"]);
+
+    let distribution = json(follower_alpha_distribution(&followers).unwrap());
+    assert_eq!(distribution, json!({ "#": 2, "a": 2, "b": 1, "é": 1 }));
+}
+
+#[wasm_bindgen_test]
+fn serialization_accepts_a_javascript_set_or_map() {
+    let names = ["alice", "bob", "carol"];
+    let from_array = serialize_followers_to_mpack(js(&names), None).unwrap();
+
+    let set = js_sys::Set::new(&js(&names));
+    assert_eq!(serialize_followers_to_mpack(set.into(), None).unwrap(), from_array);
+
+    let map = js_sys::Map::new();
+    for name in names {
+        map.set(&name.into(), &true.into());
+    }
+    assert_eq!(serialize_followers_to_mpack(map.into(), None).unwrap(), from_array);
+}
+
+#[wasm_bindgen_test]
+fn serialization_rejects_unsupported_collection_types() {
+    let message = error_message(serialize_followers_to_mpack(js(&42), None));
+    assert!(message.contains("expected an Array, Set or Map of usernames, found number"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn cohort_retention_with_half_of_the_cohort_retained() {
+    let current = mpack(&["alice", "carol", "zoe"]);
+
+    let retention = retention_of_cohort(js(&["alice", "bob", "carol", "dave", "alice"]), &current).unwrap();
+    assert_eq!(retention, 0.5);
+    assert_eq!(retention_of_cohort(js(&[] as &[&str]), &current).unwrap(), 1.0);
+}
+
+#[wasm_bindgen_test]
+fn own_username_is_stripped_from_both_lists() {
+    let blob = serialize_followers_to_mpack(js(&["alice", "Me", "bob"]), Some("me".to_string())).unwrap();
+    assert_eq!(blob, serialize_followers_to_mpack(js(&["alice", "bob"]), None).unwrap());
+
+    let old = mpack(&["alice", "ME", "bob"]);
+    let unfollowers = find_unfollowers(js(&["alice"]), &old, Some("me".to_string())).unwrap();
+    assert_eq!(json(unfollowers), json!(["bob"]));
+}
+
+#[wasm_bindgen_test]
+fn following_removed_mirrors_find_unfollowers() {
+    let old = mpack(&["alice", "bob", "carol", "bob"]);
+    let new = ["carol", "dave"];
+
+    let removed = sorted_names(find_following_removed(js(&new), &old).unwrap());
+    assert_eq!(removed, ["alice", "bob"]);
+    assert_eq!(removed, sorted_names(find_unfollowers(js(&new), &old, None).unwrap()));
+}
+
+#[wasm_bindgen_test]
+fn mutual_count_matches_the_length_of_find_mutuals() {
+    let small = ["bob", "carol", "zoe"];
+    let large = ["alice", "bob", "carol", "dave", "erin", "bob"];
+
+    for (following, followers) in [(&small[..], &large[..]), (&large[..], &small[..])] {
+        let mutuals = json(find_mutuals(js(following), &mpack(followers)).unwrap());
+        let count = mutual_count(js(following), &mpack(followers)).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(count, mutuals.as_array().unwrap().len());
+    }
+}
+
+#[wasm_bindgen_test]
+fn decode_errors_report_the_input_size() {
+    let truncated = [0x92, 0xa5, b'a', b'l'];
+    let message = error_message(find_unfollowers(js(&["alice"]), &truncated, None));
+    assert!(message.starts_with("Failed to deserialize old followers (4 bytes): "), "{}", message);
+
+    let message = error_message(retention_of_cohort(js(&json!(["alice", 7, "bob"])), &[]));
+    assert!(message.starts_with("Failed to deserialize cohort (3 elements): "), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn capped_unfollowers_report_the_true_total() {
+    let old = mpack(&["erin", "alice", "dave", "bob", "carol", "stays"]);
+
+    let capped = json(find_unfollowers_capped(js(&["stays"]), &old, 3).unwrap());
+    assert_eq!(capped, json!({ "total": 5, "shown": ["alice", "bob", "carol"] }));
+
+    let uncapped = json(find_unfollowers_capped(js(&["stays"]), &old, 10).unwrap());
+    assert_eq!(uncapped, json!({ "total": 5, "shown": ["alice", "bob", "carol", "dave", "erin"] }));
+}
+
+#[wasm_bindgen_test]
+fn excluded_accounts_that_vanished_are_not_reported() {
+    let old = mpack(&["alice", "blocked", "bob", "private"]);
+    let new = js(&["alice"]);
+
+    let unfollowers = json(find_unfollowers_excluding(new, &old, js(&["blocked", "private", "never_seen"])).unwrap());
+    assert_eq!(unfollowers, json!(["bob"]));
+}
+
+#[wasm_bindgen_test]
+fn fans_i_dropped_still_follow_me() {
+    let prev_following = mpack(&["fan", "stranger", "kept"]);
+    let new_following = js(&["kept"]);
+    let followers = mpack(&["fan", "kept", "someone"]);
+
+    let dropped = json(find_fans_i_dropped(&prev_following, new_following, &followers).unwrap());
+    assert_eq!(dropped, json!(["fan"]));
+}
+
+#[wasm_bindgen_test]
+fn events_tag_both_change_types_in_username_order() {
+    let old = mpack(&["alice", "carol", "erin"]);
+    let new = js(&["alice", "bob", "dave"]);
+
+    let events = json(diff_as_events(new, &old).unwrap());
+    assert_eq!(
+        events,
+        json!([
+            { "type": "gained", "username": "bob" },
+            { "type": "lost", "username": "carol" },
+            { "type": "gained", "username": "dave" },
+            { "type": "lost", "username": "erin" },
+        ])
+    );
+}
+
+#[wasm_bindgen_test]
+fn plausible_and_implausible_successors() {
+    let old = mpack(&["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]);
+
+    // One lost and one gained: 2 changes for 10 followers.
+    let next_check = js(&["a", "b", "c", "d", "e", "f", "g", "h", "i", "k"]);
+    assert!(is_plausible_successor(next_check, &old, 0.5).unwrap());
+
+    // A different account's followers share almost nothing.
+    let other_account = js(&["a", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x8", "x9"]);
+    assert!(!is_plausible_successor(other_account, &old, 0.5).unwrap());
+
+    assert!(is_plausible_successor(js(&["anyone"]), &[], 0.0).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn unfollowers_as_profile_urls() {
+    let old = mpack(&["jane.doe", "mike_99", "stays"]);
+
+    let urls = json(find_unfollowers_as_urls(js(&["stays"]), &old, None).unwrap());
+    assert_eq!(urls, json!(["https://instagram.com/jane.doe", "https://instagram.com/mike_99"]));
+
+    let custom = find_unfollowers_as_urls(js(&["stays"]), &old, Some("https://example.com/u/".to_string()));
+    assert_eq!(json(custom.unwrap()), json!(["https://example.com/u/jane.doe", "https://example.com/u/mike_99"]));
+}
+
+#[wasm_bindgen_test]
+fn profile_urls_reject_a_bad_base_url_or_username() {
+    let (old, new) = (mpack(&["jane doe"]), js(&["someone"]));
+
+    let message = error_message(find_unfollowers_as_urls(new.clone(), &old, Some("ftp://example.com".to_string())));
+    assert!(message.starts_with("Invalid base URL 'ftp://example.com'"), "{}", message);
+    let message = error_message(find_unfollowers_as_urls(new, &old, None));
+    assert_eq!(message, "Username 'jane doe' is not URL-safe");
+}
+
+#[wasm_bindgen_test]
+fn repeat_unfollowers_appear_in_both_lists() {
+    let past = mpack(&["serial_leaver", "long_gone"]);
+
+    let repeats = json(find_repeat_unfollowers(js(&["serial_leaver", "first_timer"]), &past).unwrap());
+    assert_eq!(repeats, json!(["serial_leaver"]));
+}
@@ -0,0 +1,39 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/display.rs
+* Description: WASM tests for the display helpers in display.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{js, json};
+use serde_json::json;
+use unfollower_logic::display::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn truncates_emoji_usernames_on_grapheme_boundaries() {
+    // A family emoji (five code points), a flag (two) and an e with a combining
+    // acute accent (two) each count as one character.
+    let followers = js(&[
+        "ab\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}cd",
+        "\u{1F1E7}\u{1F1F7}\u{1F1E7}\u{1F1F7}\u{1F1E7}\u{1F1F7}\u{1F1E7}\u{1F1F7}",
+        "jose\u{301}_silva",
+        "bob",
+    ]);
+
+    let truncated = json(truncate_usernames(followers, 3, "…").unwrap());
+    assert_eq!(
+        truncated,
+        json!([
+            "ab\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}…",
+            "\u{1F1E7}\u{1F1F7}\u{1F1E7}\u{1F1F7}\u{1F1E7}\u{1F1F7}…",
+            "jos…",
+            "bob",
+        ])
+    );
+    // The accent stays with its letter at the cut.
+    let accented = json(truncate_usernames(js(&["jose\u{301}_silva"]), 4, "...").unwrap());
+    assert_eq!(accented, json!(["jose\u{301}..."]));
+}
@@ -0,0 +1,50 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/export.rs
+* Description: WASM tests for the export functions in export.rs that take
+* their follower lists as JavaScript values.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, mpack};
+use serde_json::json;
+use unfollower_logic::export::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn json_patch_removes_by_descending_index_then_appends() {
+    let old = mpack(&["alice", "bob", "carol", "dave"]);
+    let new = js(&["carol", "zoe", "alice", "erin"]);
+
+    let patch: serde_json::Value = serde_json::from_str(&diff_as_json_patch(new, &old).unwrap()).unwrap();
+    assert_eq!(
+        patch,
+        json!([
+            { "op": "remove", "path": "/followers/3", "value": "dave" },
+            { "op": "remove", "path": "/followers/1", "value": "bob" },
+            { "op": "add", "path": "/followers/-", "value": "erin" },
+            { "op": "add", "path": "/followers/-", "value": "zoe" },
+        ])
+    );
+}
+
+#[wasm_bindgen_test]
+fn json_patch_is_empty_for_unchanged_followers() {
+    let old = mpack(&["alice", "bob"]);
+    assert_eq!(diff_as_json_patch(js(&["bob", "alice"]), &old).unwrap(), "[]");
+}
+
+#[wasm_bindgen_test]
+fn text_import_rejects_a_10000_character_line() {
+    let text = format!("alice\n{}\nbob\n", "x".repeat(10_000));
+    let message = error_message(followers_from_text(&text));
+    assert!(message.starts_with("Username is too long (10000 characters, maximum is 100)"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn base64_decoding_rejects_padded_and_standard_alphabet_text() {
+    assert!(error_message(base64_to_mpack("AH+A+/7/kqU")).starts_with("Failed to decode Base64"));
+    assert!(error_message(base64_to_mpack("AA==")).starts_with("Failed to decode Base64"));
+}
@@ -0,0 +1,40 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/fingerprint.rs
+* Description: WASM tests for the fingerprint functions in fingerprint.rs
+* that take their follower lists as JavaScript values.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js};
+use unfollower_logic::fingerprint::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn each_algorithm_is_order_independent_from_javascript() {
+    for algo in ["fnv", "xxhash", "sha256"] {
+        let forward = follower_list_fingerprint_algo(js(&["alice", "bob", "carol"]), algo).unwrap();
+        let shuffled = follower_list_fingerprint_algo(js(&["carol", "bob", "alice", "bob"]), algo).unwrap();
+        assert_eq!(forward, shuffled, "{}", algo);
+    }
+    assert_eq!(
+        follower_list_fingerprint(js(&["bob", "alice"])).unwrap(),
+        follower_list_fingerprint_algo(js(&["alice", "bob"]), "fnv").unwrap()
+    );
+}
+
+#[wasm_bindgen_test]
+fn rejects_an_unknown_algorithm() {
+    let message = error_message(follower_list_fingerprint_algo(js(&["alice"]), "md5"));
+    assert_eq!(message, "Unknown fingerprint algorithm 'md5': expected \"fnv\", \"xxhash\", \"sha256\" or \"sum-v1\"");
+}
+
+#[wasm_bindgen_test]
+fn state_fed_page_by_page_matches_the_sum_v1_fingerprint() {
+    let mut state = FingerprintState::new();
+    state.update(js(&["carol", "alice"])).unwrap();
+    state.update(js(&["alice", "bob"])).unwrap();
+    assert_eq!(state.finalize(), follower_list_fingerprint_algo(js(&["alice", "bob", "carol"]), "sum-v1").unwrap());
+}
@@ -0,0 +1,111 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/follower.rs
+* Description: WASM tests for the `Follower` record functions in follower.rs
+* that take their records as JavaScript values.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, json};
+use serde_json::json;
+use unfollower_logic::follower::*;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+fn record(username: &str, user_id: u64) -> Follower {
+    Follower { username: username.to_string(), user_id, full_name: None, is_verified: None }
+}
+
+#[wasm_bindgen_test]
+fn reports_a_username_claimed_by_a_different_account() {
+    let old = serialize_follower_records_to_mpack(js(&[record("alice", 1), record("bob", 2)])).unwrap();
+    let new = js(&[record("alice", 1), record("bob", 99), record("carol", 3)]);
+
+    let takeovers = json(find_handle_takeovers(new, &old).unwrap());
+    assert_eq!(takeovers, json!([{ "username": "bob", "old_user_id": 2, "new_user_id": 99 }]));
+}
+
+#[wasm_bindgen_test]
+fn ranks_unfollowers_by_their_follower_count() {
+    let old = js(&[
+        FollowerWithCount { username: "small".to_string(), followers: 10 },
+        FollowerWithCount { username: "huge".to_string(), followers: 1_000_000 },
+        FollowerWithCount { username: "stays".to_string(), followers: 5_000_000 },
+        FollowerWithCount { username: "tie_b".to_string(), followers: 500 },
+        FollowerWithCount { username: "tie_a".to_string(), followers: 500 },
+    ]);
+    let new = js(&["stays"]);
+
+    let ranked = json(find_unfollowers_ranked(new, old).unwrap());
+    assert_eq!(
+        ranked,
+        json!([
+            { "username": "huge", "followers": 1_000_000 },
+            { "username": "tie_a", "followers": 500 },
+            { "username": "tie_b", "followers": 500 },
+            { "username": "small", "followers": 10 },
+        ])
+    );
+}
+
+#[wasm_bindgen_test]
+fn record_inputs_reject_a_10000_character_username() {
+    let long = "x".repeat(10_000);
+    let message = error_message(serialize_follower_records_to_mpack(js(&[record(&long, 1)])));
+    assert!(message.starts_with("Username is too long (10000 characters, maximum is 100)"), "{}", message);
+
+    let old = serialize_follower_records_to_mpack(js(&[record("alice", 1)])).unwrap();
+    assert!(find_handle_takeovers(js(&[record(&long, 1)]), &old).is_err());
+}
+
+fn scored(username: &str, confidence: f64) -> ScoredFollower {
+    ScoredFollower { username: username.to_string(), confidence }
+}
+
+#[wasm_bindgen_test]
+fn low_confidence_new_entries_are_ignored_when_deciding_absences() {
+    let old = js(&[scored("alice", 0.9), scored("bob", 0.8), scored("carol", 0.2)]);
+    let old = serialize_followers_scored(old).unwrap();
+    let new = [scored("alice", 0.9), scored("bob", 0.2), scored("mallory", 0.1)];
+
+    let unfollowers = json(find_unfollowers_min_confidence(js(&new), &old, 0.5).unwrap());
+    assert_eq!(unfollowers, json!(["bob", "carol"]));
+    let unfollowers = json(find_unfollowers_min_confidence(js(&new), &old, 0.1).unwrap());
+    assert_eq!(unfollowers, json!(["carol"]));
+}
+
+#[wasm_bindgen_test]
+fn blobs_of_the_other_layout_report_a_wrong_format() {
+    let records = serialize_follower_records_to_mpack(js(&[record("alice", 1)])).unwrap();
+    let message = error_message(unfollower_logic::find_unfollowers(js(&["alice"]), &records, None));
+    assert_eq!(message, "Wrong blob format: expected username list, found follower records");
+
+    let usernames = rmp_serde::to_vec(&["alice"]).unwrap();
+    let message = error_message(find_handle_takeovers(js(&[record("alice", 1)]), &usernames));
+    assert_eq!(message, "Wrong blob format: expected follower records, found username list");
+}
+
+#[wasm_bindgen_test]
+fn compressed_and_signed_blobs_report_a_wrong_format() {
+    let zlib = [0x78, 0x9c, 0x8b, 0x4e, 0xcc, 0xc9, 0x4c, 0x4e, 0x05, 0x00];
+    let message = error_message(unfollower_logic::find_unfollowers(js(&["alice"]), &zlib, None));
+    assert_eq!(message, "Wrong blob format: expected username list, found compressed blob");
+
+    let records = serialize_follower_records_to_mpack(js(&[record("alice", 1)])).unwrap();
+    let signed = unfollower_logic::signing::sign_mpack(&records, JsValue::from_str("key")).unwrap();
+    let message = error_message(find_handle_takeovers(js(&[record("alice", 1)]), &signed));
+    assert_eq!(message, "Wrong blob format: expected follower records, found signed blob");
+
+    let message = error_message(unfollower_logic::format::blobs_equivalent(&zlib, &records));
+    assert_eq!(message, "Wrong blob format: expected username list or follower records, found compressed blob");
+}
+
+#[wasm_bindgen_test]
+fn mixed_comparison_matches_records_against_old_usernames() {
+    let old = rmp_serde::to_vec(&["alice", "bob", "carol"]).unwrap();
+    let new = js(&[record("carol", 3), record("alice", 1), record("dave", 4)]);
+
+    assert_eq!(json(find_unfollowers_mixed(new, &old).unwrap()), json!(["bob"]));
+}
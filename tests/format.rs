@@ -0,0 +1,63 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/format.rs
+* Description: WASM tests for the layout-aware blob functions in format.rs
+* that return JavaScript values.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, json, mpack};
+use serde_json::json;
+use unfollower_logic::follower::Follower;
+use unfollower_logic::format::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn inspect_reports_empty_valid_and_corrupt_blobs() {
+    assert_eq!(json(inspect_blob(&[]).unwrap()), json!({ "state": "empty" }));
+    assert_eq!(json(inspect_blob(&mpack(&["alice", "bob"])).unwrap()), json!({ "state": "valid", "count": 2 }));
+
+    let corrupt = json(inspect_blob(&[0x92, 0xa5, b'a']).unwrap());
+    assert_eq!(corrupt["state"], "corrupt");
+    assert_eq!(corrupt["count"], serde_json::Value::Null);
+    let detail = corrupt["detail"].as_str().unwrap();
+    assert!(detail.starts_with("Failed to deserialize stored blob (3 bytes)"), "{}", detail);
+}
+
+#[wasm_bindgen_test]
+fn sorts_usernames_ascending_and_descending() {
+    let blob = mpack(&["carol", "alice", "dave", "bob"]);
+
+    assert_eq!(json(sort_followers(&blob, "username", false).unwrap()), json!(["alice", "bob", "carol", "dave"]));
+    assert_eq!(json(sort_followers(&blob, "username", true).unwrap()), json!(["dave", "carol", "bob", "alice"]));
+}
+
+#[wasm_bindgen_test]
+fn sorting_rejects_an_unknown_key() {
+    let message = error_message(sort_followers(&mpack(&["alice"]), "first_seen", false));
+    assert_eq!(message, "Unknown sort key 'first_seen': expected \"username\" or \"full_name\"");
+}
+
+#[wasm_bindgen_test]
+fn descending_full_name_sort_reverses_ties_too() {
+    let record = |username: &str, full_name: Option<&str>| Follower {
+        username: username.to_string(),
+        user_id: 1,
+        full_name: full_name.map(str::to_string),
+        is_verified: None,
+    };
+    let blob = mpack(&[
+        record("bob", Some("Sam")),
+        record("zoe", None),
+        record("alice", Some("Sam")),
+        record("carol", Some("Ann")),
+        record("dave", None),
+    ]);
+
+    let ascending = json!(["carol", "alice", "bob", "dave", "zoe"]);
+    let descending = json!(["bob", "alice", "carol", "zoe", "dave"]);
+    assert_eq!(json(sort_followers(&blob, "full_name", false).unwrap()), ascending);
+    assert_eq!(json(sort_followers(&blob, "full_name", true).unwrap()), descending);
+}
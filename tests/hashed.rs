@@ -0,0 +1,34 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/hashed.rs
+* Description: WASM tests for the salted snapshots in hashed.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, json};
+use serde_json::json;
+use unfollower_logic::hashed::*;
+use wasm_bindgen_test::*;
+
+const BOB_SALTED: &str = "876ccb7de6bc3ec9b3a2cdc487035159f0121db7e0cd844d26b33cf7fcb4c9dd";
+
+#[wasm_bindgen_test]
+fn hashed_snapshots_are_consistent_across_calls() {
+    let first = serialize_hashed_followers(js(&["alice", "bob"]), "salt").unwrap();
+    let second = serialize_hashed_followers(js(&["bob", "alice", "bob"]), "salt").unwrap();
+    assert_eq!(first, second);
+
+    let departed = json(find_unfollowers_hashed(js(&["alice", "carol"]), &first, "salt").unwrap());
+    assert_eq!(departed, json!([BOB_SALTED]));
+
+    // With a different salt nothing matches, so everyone looks departed.
+    let mismatched = json(find_unfollowers_hashed(js(&["alice", "bob"]), &first, "pepper").unwrap());
+    assert_eq!(mismatched.as_array().unwrap().len(), 2);
+}
+
+#[wasm_bindgen_test]
+fn rejects_an_empty_salt() {
+    assert_eq!(error_message(serialize_hashed_followers(js(&["alice"]), "")), "Salt must not be empty");
+}
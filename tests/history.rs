@@ -0,0 +1,164 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/history.rs
+* Description: WASM tests for the history log queries in history.rs that
+* return JavaScript values.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, json, mpack};
+use serde_json::json;
+use unfollower_logic::history::*;
+use wasm_bindgen_test::*;
+
+fn entry(timestamp: f64, follower_count: usize, new_followers: &[&str], unfollowers: &[&str]) -> HistoryEntry {
+    HistoryEntry {
+        timestamp,
+        follower_count,
+        new_followers: new_followers.iter().map(|s| s.to_string()).collect(),
+        unfollowers: unfollowers.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+/// Four checks: bob leaves and comes back, carol leaves, and alice and bob
+/// leave at the last check.
+fn four_checks() -> Vec<u8> {
+    mpack(&[
+        entry(100.0, 3, &["alice", "bob", "carol"], &[]),
+        entry(200.0, 3, &["dave"], &["bob"]),
+        entry(300.0, 3, &["bob"], &["carol"]),
+        entry(400.0, 2, &["erin"], &["alice", "bob"]),
+    ])
+}
+
+#[wasm_bindgen_test]
+fn peak_event_is_the_check_with_the_most_unfollowers() {
+    let history = mpack(&[
+        entry(1.0, 10, &[], &["a"]),
+        entry(2.0, 5, &[], &["b", "c", "d", "e", "f"]),
+        entry(3.0, 4, &[], &["g"]),
+        entry(4.0, 4, &[], &[]),
+    ]);
+
+    let peak = json(peak_unfollower_event(&history).unwrap());
+    assert_eq!(
+        peak,
+        json!({ "timestamp": 2, "follower_count": 5, "new_followers": [], "unfollowers": ["b", "c", "d", "e", "f"] })
+    );
+}
+
+#[wasm_bindgen_test]
+fn peak_event_ties_resolve_to_the_earliest_and_empty_is_null() {
+    let history = mpack(&[entry(5.0, 1, &[], &["late"]), entry(3.0, 2, &[], &["early"])]);
+    assert_eq!(json(peak_unfollower_event(&history).unwrap())["unfollowers"], json!(["early"]));
+
+    assert!(peak_unfollower_event(&[]).unwrap().is_null());
+}
+
+#[wasm_bindgen_test]
+fn history_functions_reject_nan_and_negative_times() {
+    let message = error_message(append_history_entry(&[], js(&["alice"]), &[], f64::NAN));
+    assert!(message.starts_with("Invalid timestamp for 'timestamp': NaN"), "{}", message);
+
+    let message = error_message(append_history_entry(&[], js(&["alice"]), &[], -5.0));
+    assert!(message.starts_with("Invalid timestamp for 'timestamp': -5"), "{}", message);
+
+    let history = mpack(&[entry(1.0, 1, &["alice"], &[])]);
+    assert!(error_message(growth_rate(&history, -1.0, 2.0)).starts_with("Invalid duration for 'window_ms'"));
+    assert!(error_message(growth_rate(&history, 1.0, f64::INFINITY)).starts_with("Invalid timestamp for 'now'"));
+    let message = error_message(unfollowers_in_range(&history, f64::NAN, 2.0));
+    assert!(message.starts_with("Invalid timestamp for 'start'"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn milestones_report_the_first_crossing_of_two_of_three_thresholds() {
+    let history = mpack(&[
+        entry(3.0, 520, &[], &[]),
+        entry(1.0, 90, &[], &[]),
+        entry(2.0, 150, &[], &[]),
+        entry(4.0, 480, &[], &[]),
+        entry(5.0, 700, &[], &[]),
+    ]);
+
+    let milestones = json(follower_milestones(&history, js(&[1000, 100, 500])).unwrap());
+    assert_eq!(milestones, json!([{ "milestone": 100, "timestamp": 2 }, { "milestone": 500, "timestamp": 3 }]));
+}
+
+#[wasm_bindgen_test]
+fn recent_gains_lists_the_last_three_of_five_checks_newest_first() {
+    let history = mpack(&[
+        entry(1.0, 1, &["a"], &[]),
+        entry(2.0, 2, &["b"], &[]),
+        entry(3.0, 4, &["c", "d"], &[]),
+        entry(4.0, 4, &[], &[]),
+        entry(5.0, 5, &["e"], &[]),
+    ]);
+
+    let gains = json(recent_gains(&history, 3).unwrap());
+    assert_eq!(
+        gains,
+        json!([
+            { "timestamp": 5, "usernames": ["e"] },
+            { "timestamp": 4, "usernames": [] },
+            { "timestamp": 3, "usernames": ["c", "d"] },
+        ])
+    );
+    assert_eq!(json(recent_gains(&history, 10).unwrap()).as_array().unwrap().len(), 5);
+}
+
+#[wasm_bindgen_test]
+fn reconstructs_the_followers_in_the_middle_of_the_history() {
+    let history = four_checks();
+
+    assert_eq!(json(reconstruct_at(&history, 250.0).unwrap()), json!(["alice", "carol", "dave"]));
+    assert_eq!(json(reconstruct_at(&history, 300.0).unwrap()), json!(["alice", "bob", "dave"]));
+    assert_eq!(json(reconstruct_at(&history, 50.0).unwrap()), json!([]));
+    assert_eq!(json(reconstruct_at(&history, 1_000.0).unwrap()), json!(["dave", "erin"]));
+
+    let message = error_message(reconstruct_at(&history, f64::NAN));
+    assert!(message.starts_with("Invalid timestamp for 'timestamp'"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn all_time_unfollowers_lists_repeat_leavers_once() {
+    assert_eq!(json(all_time_unfollowers(&four_checks()).unwrap()), json!(["alice", "bob", "carol"]));
+    assert_eq!(json(all_time_unfollowers(&[]).unwrap()), json!([]));
+}
+
+#[wasm_bindgen_test]
+fn half_life_needs_a_cohort_that_lost_half() {
+    let history = mpack(&[entry(1.0, 2, &["alice", "bob"], &[]), entry(2.0, 3, &["carol"], &[])]);
+    let message = error_message(retention_half_life(&history));
+    assert!(message.starts_with("Cannot compute retention half-life"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn compares_two_non_adjacent_snapshots() {
+    // 110 and 390 are nearest to the first and last checks.
+    let comparison = json(compare_snapshots_at(&four_checks(), 110.0, 390.0).unwrap());
+    assert_eq!(
+        comparison,
+        json!({
+            "timestamp_a": 100,
+            "timestamp_b": 400,
+            "gained": ["dave", "erin"],
+            "lost": ["alice", "bob", "carol"],
+            "net_change": -1,
+        })
+    );
+
+    let message = error_message(compare_snapshots_at(&[], 1.0, 2.0));
+    assert_eq!(message, "Cannot compare snapshots: history is empty");
+}
+
+#[wasm_bindgen_test]
+fn unfollowers_in_range_include_both_ends_and_skip_the_rest() {
+    let history = four_checks();
+
+    assert_eq!(json(unfollowers_in_range(&history, 200.0, 300.0).unwrap()), json!(["bob", "carol"]));
+    assert_eq!(json(unfollowers_in_range(&history, 201.0, 299.0).unwrap()), json!([]));
+    assert_eq!(json(unfollowers_in_range(&history, 250.0, 1_000.0).unwrap()), json!(["alice", "bob", "carol"]));
+    assert_eq!(json(unfollowers_in_range(&history, 300.0, 200.0).unwrap()), json!([]));
+}
@@ -0,0 +1,39 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/notes.rs
+* Description: WASM tests for the noted snapshots in notes.rs, which take the
+* latest `Follower` records as JavaScript values.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::js;
+use unfollower_logic::follower::Follower;
+use unfollower_logic::notes::*;
+use wasm_bindgen_test::*;
+
+fn record(username: &str, user_id: u64) -> Follower {
+    Follower { username: username.to_string(), user_id, full_name: None, is_verified: None }
+}
+
+fn decode(snapshot: &[u8]) -> NotedSnapshot {
+    rmp_serde::from_slice(snapshot).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn a_note_survives_an_update_where_the_account_remains() {
+    let first = update_followers_preserving_notes(js(&[record("alice", 1), record("bob", 2)]), &[], false).unwrap();
+    let noted = set_follower_note(&first, "1", Some("met at conference".to_string())).unwrap();
+    let noted = set_follower_note(&noted, "2", Some("old classmate".to_string())).unwrap();
+
+    // alice changed handle and bob left.
+    let updated = update_followers_preserving_notes(js(&[record("alice_new", 1)]), &noted, false).unwrap();
+    let snapshot = decode(&updated);
+    assert_eq!(snapshot.followers, [record("alice_new", 1)]);
+    assert_eq!(snapshot.notes.len(), 1);
+    assert_eq!(snapshot.notes["1"], "met at conference");
+
+    let kept = decode(&update_followers_preserving_notes(js(&[record("alice", 1)]), &noted, true).unwrap());
+    assert_eq!(kept.notes["2"], "old classmate");
+}
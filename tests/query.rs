@@ -0,0 +1,57 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/query.rs
+* Description: WASM tests for the follower query language in query.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, json, mpack};
+use serde_json::json;
+use unfollower_logic::follower::Follower;
+use unfollower_logic::query::*;
+use wasm_bindgen_test::*;
+
+fn followers() -> Vec<u8> {
+    mpack(&["john_doe", "Johnathan_Smith_99", "nasa_official", "official_fan", "zed"])
+}
+
+#[wasm_bindgen_test]
+fn filters_with_each_operator() {
+    let blob = followers();
+    let filter = |query| json(filter_followers(&blob, query).unwrap());
+
+    assert_eq!(filter("startswith:john"), json!(["Johnathan_Smith_99", "john_doe"]));
+    assert_eq!(filter("contains:official"), json!(["nasa_official", "official_fan"]));
+    assert_eq!(filter("len>15"), json!(["Johnathan_Smith_99"]));
+    assert_eq!(filter("len<4"), json!(["zed"]));
+    assert_eq!(filter("len=8"), json!(["john_doe"]));
+    assert_eq!(filter("startswith:john len<10"), json!(["john_doe"]));
+}
+
+#[wasm_bindgen_test]
+fn verified_filter_reads_follower_records() {
+    let record = |username: &str, is_verified| Follower {
+        username: username.to_string(),
+        user_id: 1,
+        full_name: None,
+        is_verified,
+    };
+    let blob = mpack(&[record("nasa", Some(true)), record("fan", Some(false)), record("unknown", None)]);
+
+    assert_eq!(json(filter_followers(&blob, "verified:true").unwrap()), json!(["nasa"]));
+    assert_eq!(json(filter_followers(&blob, "verified:false").unwrap()), json!(["fan", "unknown"]));
+}
+
+#[wasm_bindgen_test]
+fn rejects_unknown_and_malformed_queries() {
+    let blob = followers();
+    let message = error_message(filter_followers(&blob, "endswith:doe"));
+    assert!(message.starts_with("Unknown query term 'endswith:doe'"), "{}", message);
+    let message = error_message(filter_followers(&blob, "len>>3"));
+    assert!(message.starts_with("Invalid query term 'len>>3'"), "{}", message);
+    assert_eq!(error_message(filter_followers(&blob, "  ")), "Query is empty: expected at least one term");
+    let message = error_message(filter_followers(&blob, "verified:true"));
+    assert!(message.starts_with("Wrong blob format"), "{}", message);
+}
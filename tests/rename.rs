@@ -0,0 +1,43 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/rename.rs
+* Description: WASM tests for the rename-aware diff in rename.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{js, json, mpack};
+use serde_json::json;
+use unfollower_logic::rename::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn pairs_a_distance_2_rename() {
+    let old = mpack(&["alice", "jane.doe", "zed"]);
+    let new = js(&["alice", "jane_doe_", "totally_new"]);
+
+    let diff = json(diff_with_rename_detection(new.clone(), &old, 2).unwrap());
+    assert_eq!(
+        diff,
+        json!({
+            "unfollowers": ["zed"],
+            "new_followers": ["totally_new"],
+            "likely_renames": [{ "from": "jane.doe", "to": "jane_doe_" }],
+        })
+    );
+
+    let strict = json(diff_with_rename_detection(new, &old, 1).unwrap());
+    assert_eq!(strict["likely_renames"], json!([]));
+}
+
+#[wasm_bindgen_test]
+fn a_mass_change_returns_the_plain_diff_without_renames() {
+    let old: Vec<String> = (0..2000).map(|i| format!("old_account_{:04}", i)).collect();
+    let new: Vec<String> = (0..2000).map(|i| format!("new_account_{:04}", i)).collect();
+
+    let diff = json(diff_with_rename_detection(js(&new), &mpack(&old), 3).unwrap());
+    assert_eq!(diff["likely_renames"], json!([]));
+    assert_eq!(diff["unfollowers"].as_array().unwrap().len(), 2000);
+    assert_eq!(diff["new_followers"].as_array().unwrap().len(), 2000);
+}
@@ -0,0 +1,44 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/report.rs
+* Description: WASM tests for the shareable report cards in report.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, json, mpack};
+use serde_json::json;
+use unfollower_logic::history::HistoryEntry;
+use unfollower_logic::report::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn report_card_reads_back_as_an_object() {
+    let history = mpack(&[HistoryEntry {
+        timestamp: 1_000.0,
+        follower_count: 42,
+        new_followers: vec!["alice".to_string()],
+        unfollowers: vec!["bob".to_string()],
+    }]);
+
+    let card = generate_report_card(&history, 2_000.0).unwrap();
+    assert_eq!(
+        json(read_report_card(&card).unwrap()),
+        json!({
+            "generated_at": 2000,
+            "total_followers": 42,
+            "net_change_week": 0,
+            "net_change_month": 0,
+            "biggest_drop": 1,
+            "all_time_unfollowers": 1,
+        })
+    );
+}
+
+#[wasm_bindgen_test]
+fn report_card_needs_history_and_a_card_blob() {
+    assert_eq!(error_message(generate_report_card(&[], 1.0)), "Cannot generate report card: history is empty");
+    let message = error_message(read_report_card(&mpack(&["alice"])));
+    assert!(message.starts_with("Failed to deserialize report card (7 bytes)"), "{}", message);
+}
@@ -0,0 +1,33 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/sampling.rs
+* Description: WASM tests for the seeded sampling in sampling.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{json, mpack};
+use unfollower_logic::sampling::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn same_seed_yields_the_same_sample() {
+    let followers: Vec<String> = (0..50).map(|i| format!("user{}", i)).collect();
+    let blob = mpack(&followers);
+
+    let sample = json(sample_followers(&blob, 5, 1234).unwrap());
+    assert_eq!(sample, json(sample_followers(&blob, 5, 1234).unwrap()));
+    assert_ne!(sample, json(sample_followers(&blob, 5, 4321).unwrap()));
+    assert_eq!(sample.as_array().unwrap().len(), 5);
+}
+
+#[wasm_bindgen_test]
+fn sample_never_repeats_an_account() {
+    let blob = mpack(&["alice", "alice", "bob", "bob", "carol"]);
+    let sample = json(sample_followers(&blob, 10, 1).unwrap());
+
+    let mut names: Vec<&str> = sample.as_array().unwrap().iter().map(|v| v.as_str().unwrap()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["alice", "bob", "carol"]);
+}
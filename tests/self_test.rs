@@ -0,0 +1,31 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/self_test.rs
+* Description: WASM test for `self_test` in self_test.rs, which also runs the
+* checks that cross the JS/WASM boundary.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::json;
+use serde_json::json;
+use unfollower_logic::self_test::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn self_test_passes_on_a_healthy_build() {
+    assert_eq!(
+        json(self_test().unwrap()),
+        json!({
+            "ok": true,
+            "checks": [
+                "js_round_trip",
+                "mpack_round_trip",
+                "unfollower_diff",
+                "new_follower_diff",
+                "fingerprint_order_independent",
+            ],
+        })
+    );
+}
@@ -0,0 +1,28 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/serializer.rs
+* Description: WASM tests for the buffer-reusing `FollowerSerializer` in
+* serializer.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{js, mpack};
+use unfollower_logic::serializer::FollowerSerializer;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn serializes_several_lists_through_one_instance() {
+    let mut serializer = FollowerSerializer::new();
+
+    let first = serializer.serialize_into(js(&["alice", "bob", "carol"])).unwrap();
+    let second = serializer.serialize_into(js(&["dave"])).unwrap();
+    let third = serializer.serialize_into(js(&["erin", "frank"])).unwrap();
+
+    // Each result is an independent copy: earlier ones are not overwritten by
+    // later, shorter serializations.
+    assert_eq!(first.to_vec(), mpack(&["alice", "bob", "carol"]));
+    assert_eq!(second.to_vec(), mpack(&["dave"]));
+    assert_eq!(third.to_vec(), mpack(&["erin", "frank"]));
+}
@@ -0,0 +1,69 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/set_ops.rs
+* Description: WASM tests for the set-algebra functions in set_ops.rs that
+* take their operands as JavaScript values.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js, json, mpack};
+use serde_json::json;
+use unfollower_logic::set_ops::*;
+use wasm_bindgen_test::*;
+
+const EMPTY: [&str; 0] = [];
+
+#[wasm_bindgen_test]
+fn union_is_sorted_and_deduplicated() {
+    let union = set_union(js(&["carol", "alice", "alice"]), js(&["bob", "carol"])).unwrap();
+    assert_eq!(json(union), json!(["alice", "bob", "carol"]));
+}
+
+#[wasm_bindgen_test]
+fn intersection_keeps_shared_usernames() {
+    let intersection = set_intersection(js(&["carol", "alice", "bob"]), js(&["bob", "carol", "dave"])).unwrap();
+    assert_eq!(json(intersection), json!(["bob", "carol"]));
+}
+
+#[wasm_bindgen_test]
+fn difference_is_one_sided() {
+    let difference = set_difference(js(&["carol", "alice", "bob"]), js(&["bob", "dave"])).unwrap();
+    assert_eq!(json(difference), json!(["alice", "carol"]));
+}
+
+#[wasm_bindgen_test]
+fn operations_on_empty_inputs() {
+    let names = js(&["bob", "alice"]);
+    assert_eq!(json(set_union(js(&EMPTY), js(&EMPTY)).unwrap()), json!([]));
+    assert_eq!(json(set_union(js(&EMPTY), names.clone()).unwrap()), json!(["alice", "bob"]));
+    assert_eq!(json(set_intersection(names.clone(), js(&EMPTY)).unwrap()), json!([]));
+    assert_eq!(json(set_difference(names.clone(), js(&EMPTY)).unwrap()), json!(["alice", "bob"]));
+    assert_eq!(json(set_difference(js(&EMPTY), names).unwrap()), json!([]));
+}
+
+#[wasm_bindgen_test]
+fn overlap_matrix_for_three_accounts_with_known_overlap() {
+    let accounts = js(&json!([
+        { "account_id": "main", "followers": ["a", "b", "c", "d"] },
+        { "account_id": "side", "followers": ["a", "b"] },
+        { "account_id": "fresh", "followers": ["x", "y"] },
+    ]));
+
+    let overlap = json(audience_overlap_matrix(accounts).unwrap());
+    assert_eq!(
+        overlap,
+        json!({
+            "account_ids": ["main", "side", "fresh"],
+            "matrix": [[1, 0.5, 0], [0.5, 1, 0], [0, 0, 1]],
+        })
+    );
+}
+
+#[wasm_bindgen_test]
+fn blob_set_op_rejects_an_unknown_operation() {
+    let a = mpack(&["alice"]);
+    let message = error_message(mpack_set_op(&a, &a, "xor"));
+    assert_eq!(message, "Unknown set operation 'xor': expected \"union\", \"intersection\" or \"difference\"");
+}
@@ -0,0 +1,49 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/signing.rs
+* Description: WASM tests for blob signing in signing.rs, whose keys are
+* JavaScript strings or `Uint8Array`s.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, mpack};
+use js_sys::Uint8Array;
+use unfollower_logic::signing::*;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+const BAD_SIGNATURE: &str = "Signature verification failed: the blob was altered or signed with a different key";
+
+fn key() -> JsValue {
+    JsValue::from_str("shared secret")
+}
+
+#[wasm_bindgen_test]
+fn signed_blob_verifies_and_returns_the_inner_blob() {
+    let blob = mpack(&["alice", "bob"]);
+    let signed = sign_mpack(&blob, key()).unwrap();
+    assert_eq!(signed.len(), blob.len() + 32);
+    assert_eq!(verify_signed_mpack(&signed, key()).unwrap(), blob);
+
+    let bytes_key = Uint8Array::from(&b"shared secret"[..]);
+    assert_eq!(verify_signed_mpack(&signed, bytes_key.into()).unwrap(), blob);
+}
+
+#[wasm_bindgen_test]
+fn altering_any_single_byte_fails_verification() {
+    let signed = sign_mpack(&mpack(&["alice", "bob"]), key()).unwrap();
+    for index in 0..signed.len() {
+        let mut tampered = signed.clone();
+        tampered[index] ^= 0x01;
+        assert_eq!(error_message(verify_signed_mpack(&tampered, key())), BAD_SIGNATURE);
+    }
+}
+
+#[wasm_bindgen_test]
+fn wrong_key_or_truncated_blob_fails_verification() {
+    let signed = sign_mpack(&mpack(&["alice"]), key()).unwrap();
+    assert_eq!(error_message(verify_signed_mpack(&signed, JsValue::from_str("other"))), BAD_SIGNATURE);
+    assert_eq!(error_message(verify_signed_mpack(&signed[..16], key())), BAD_SIGNATURE);
+}
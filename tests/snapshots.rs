@@ -0,0 +1,28 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/snapshots.rs
+* Description: WASM tests for the snapshot series queries in snapshots.rs
+* that return JavaScript values.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{json, mpack};
+use serde_json::json;
+use unfollower_logic::snapshots::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn window_stats_cover_only_the_last_three_of_ten_snapshots() {
+    // Each scrape drops its oldest follower and gains two new ones.
+    let snapshots: Vec<Snapshot> = (0..10)
+        .map(|i| Snapshot { timestamp: i as f64, followers: (i..2 * i + 1).map(|n| format!("user{}", n)).collect() })
+        .collect();
+
+    let stats = json(windowed_stats(&mpack(&snapshots), 3).unwrap());
+    assert_eq!(stats, json!({ "snapshots": 3, "gained": 4, "lost": 2, "net_change": 2 }));
+
+    let whole = json(windowed_stats(&mpack(&snapshots), 50).unwrap());
+    assert_eq!(whole, json!({ "snapshots": 10, "gained": 18, "lost": 9, "net_change": 9 }));
+}
@@ -0,0 +1,36 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/state.rs
+* Description: WASM tests for the module-level state in state.rs. The logger
+* is a JavaScript function, so they run under Node.js with
+* `wasm-pack test --node`.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{js, mpack};
+use js_sys::{Array, Function};
+use serde_json::json;
+use unfollower_logic::state::{reset_state, set_logger};
+use unfollower_logic::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn reset_state_turns_logging_into_a_no_op() {
+    let old = mpack(&["alice", "bob"]);
+    let incomplete = || js(&json!({ "complete": false, "followers": ["alice"] }));
+    let messages = Array::new();
+    set_logger(Function::new_with_args("message", "this.push(message)").bind0(&messages));
+
+    find_unfollowers(incomplete(), &old, None).unwrap();
+    assert_eq!(messages.length(), 1);
+    assert_eq!(
+        messages.get(0).as_string().unwrap(),
+        "new followers list is incomplete, skipping removal detection"
+    );
+
+    reset_state();
+    find_unfollowers(incomplete(), &old, None).unwrap();
+    assert_eq!(messages.length(), 1);
+}
@@ -0,0 +1,47 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/storage.rs
+* Description: WASM tests for the storage-usage diagnostics in storage.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{js, json};
+use unfollower_logic::follower::Follower;
+use unfollower_logic::storage::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn a_realistic_list_compresses_and_reports_its_ratio() {
+    let followers: Vec<String> = (0..500)
+        .map(|i| format!("{}.{}_{}", ["jane", "mike", "ana", "li"][i % 4], ["doe", "silva", "smith"][i % 3], i))
+        .collect();
+
+    let stats = json(compression_stats(js(&followers)).unwrap());
+    let raw = stats["raw_mpack_bytes"].as_u64().unwrap();
+    let compressed = stats["compressed_bytes"].as_u64().unwrap();
+    assert_eq!(raw as usize, rmp_serde::to_vec(&followers).unwrap().len());
+    assert!(compressed <= raw, "{} > {}", compressed, raw);
+    assert_eq!(stats["ratio"].as_f64().unwrap(), compressed as f64 / raw as f64);
+}
+
+#[wasm_bindgen_test]
+fn id_storage_is_smaller_for_long_usernames() {
+    let followers: Vec<Follower> = (0..20)
+        .map(|i| Follower {
+            username: format!("a_really_long_instagram_handle_number_{:02}", i),
+            user_id: 48_000_000_000 + i,
+            full_name: None,
+            is_verified: None,
+        })
+        .collect();
+
+    let savings = json(id_storage_savings(js(&followers)).unwrap());
+    let username_bytes = savings["username_bytes"].as_u64().unwrap() as f64;
+    let id_bytes = savings["id_bytes"].as_u64().unwrap() as f64;
+    // 20 ids of 9 bytes each plus a 3-byte array header.
+    assert_eq!(id_bytes, 183.0);
+    assert!(id_bytes < username_bytes / 4.0);
+    assert_eq!(savings["savings_pct"].as_f64().unwrap(), (1.0 - id_bytes / username_bytes) * 100.0);
+}
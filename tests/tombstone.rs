@@ -0,0 +1,59 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/tombstone.rs
+* Description: WASM tests for the tombstone-aware snapshots in tombstone.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{error_message, js};
+use unfollower_logic::tombstone::*;
+use wasm_bindgen_test::*;
+
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+fn decode(snapshot: &[u8]) -> Vec<TrackedFollower> {
+    rmp_serde::from_slice(snapshot).unwrap()
+}
+
+fn tracked(username: &str, removed_at: Option<f64>) -> TrackedFollower {
+    TrackedFollower { username: username.to_string(), removed_at }
+}
+
+#[wasm_bindgen_test]
+fn refollow_within_grace_restores_the_account() {
+    let first = update_with_tombstones(js(&["alice", "bob"]), &[], 0.0, 7.0 * DAY_MS).unwrap();
+    let missing = update_with_tombstones(js(&["alice"]), &first, DAY_MS, 7.0 * DAY_MS).unwrap();
+    assert_eq!(decode(&missing), [tracked("alice", None), tracked("bob", Some(DAY_MS))]);
+
+    let back = update_with_tombstones(js(&["alice", "bob"]), &missing, 3.0 * DAY_MS, 7.0 * DAY_MS).unwrap();
+    assert_eq!(decode(&back), [tracked("alice", None), tracked("bob", None)]);
+}
+
+#[wasm_bindgen_test]
+fn tombstone_is_purged_after_grace() {
+    let first = update_with_tombstones(js(&["alice", "bob"]), &[], 0.0, 7.0 * DAY_MS).unwrap();
+    let missing = update_with_tombstones(js(&["alice"]), &first, DAY_MS, 7.0 * DAY_MS).unwrap();
+
+    let within = update_with_tombstones(js(&["alice"]), &missing, 8.0 * DAY_MS, 7.0 * DAY_MS).unwrap();
+    assert_eq!(decode(&within), [tracked("alice", None), tracked("bob", Some(DAY_MS))]);
+
+    let purged = update_with_tombstones(js(&["alice"]), &within, 8.0 * DAY_MS + 1.0, 7.0 * DAY_MS).unwrap();
+    assert_eq!(decode(&purged), [tracked("alice", None)]);
+}
+
+#[wasm_bindgen_test]
+fn rejects_nan_and_negative_times() {
+    let message = error_message(update_with_tombstones(js(&["alice"]), &[], f64::NAN, DAY_MS));
+    assert_eq!(
+        message,
+        "Invalid timestamp for 'now': NaN (expected a finite, non-negative number of milliseconds)"
+    );
+
+    let message = error_message(update_with_tombstones(js(&["alice"]), &[], 0.0, -1.0));
+    assert_eq!(
+        message,
+        "Invalid duration for 'grace_ms': -1 (expected a finite, non-negative number of milliseconds)"
+    );
+}
@@ -0,0 +1,33 @@
+/* Copyright (C) 2025 Pedro Henrique / phkaiser13
+* File: tests/tracker.rs
+* Description: WASM tests for the stateful `Tracker` in tracker.rs.
+* SPDX-License-Identifier: Apache-2.0 */
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use common::{js, json, mpack};
+use serde_json::json;
+use unfollower_logic::tracker::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn two_sequential_updates_diff_against_the_adopted_state() {
+    let mut tracker = Tracker::from_mpack(&mpack(&["alice", "bob", "carol"])).unwrap();
+
+    let first = json(tracker.update(js(&["alice", "carol", "dave"])).unwrap());
+    assert_eq!(first, json!({ "unfollowers": ["bob"], "new_followers": ["dave"] }));
+
+    let second = json(tracker.update(js(&["carol", "dave", "bob"])).unwrap());
+    assert_eq!(second, json!({ "unfollowers": ["alice"], "new_followers": ["bob"] }));
+
+    assert_eq!(tracker.to_mpack().unwrap(), mpack(&["bob", "carol", "dave"]));
+}
+
+#[wasm_bindgen_test]
+fn a_rejected_update_keeps_the_held_state() {
+    let mut tracker = Tracker::from_mpack(&[]).unwrap();
+    assert!(tracker.update(js(&42)).is_err());
+    assert_eq!(tracker.to_mpack().unwrap(), mpack(&[] as &[&str]));
+}